@@ -0,0 +1,55 @@
+{
+    let id = uuid::Uuid::new_v4().to_string();
+    let created: usize = graph
+        .execute_write(|txn| {
+            let id = id.clone();
+            Box::pin(async move {
+                txn.run_queries([
+                    query("CREATE (p:Person {id: $id})").param("id", id.clone()),
+                    query("CREATE (p:Person {id: $id})").param("id", id.clone()),
+                ])
+                .await?;
+                Ok(2)
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(created, 2);
+
+    let count: i64 = graph
+        .execute_read(|txn| {
+            let id = id.clone();
+            Box::pin(async move {
+                let mut result = txn
+                    .execute(
+                        query("MATCH (p:Person) WHERE p.id = $id RETURN count(p) AS count")
+                            .param("id", id),
+                    )
+                    .await?;
+                let row = result.next(txn.handle()).await?.unwrap();
+                Ok(row.get("count")?)
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    // the transaction is rolled back when the function returns an error
+    let result = graph
+        .execute_write(|txn| {
+            let id = id.clone();
+            Box::pin(async move {
+                txn.run(query("MATCH (p:Person) WHERE p.id = $id DELETE p").param("id", id))
+                    .await?;
+                Err::<(), _>(Error::UnexpectedMessage("abort".into()))
+            })
+        })
+        .await;
+    assert!(result.is_err());
+
+    let mut result = graph
+        .execute(query("MATCH (p:Person) WHERE p.id = $id RETURN p.id").param("id", id))
+        .await
+        .unwrap();
+    assert!(result.next().await.unwrap().is_some());
+}
@@ -0,0 +1,293 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use futures::future::BoxFuture;
+
+use super::AuthToken;
+use crate::errors::{
+    Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind, Result,
+};
+
+/// Provides the credentials for all connections of a [`crate::Graph`],
+/// e.g. to rotate short-lived tokens without creating a new graph.
+///
+/// The pool asks the manager for the current token whenever it opens a connection
+/// or hands out an idle one.
+/// If the token has changed in the meantime, connections with Bolt 5.1 or later
+/// authenticate again in place, older connections are closed and replaced.
+///
+/// A static token is used unless a manager is configured with
+/// [`crate::ConfigBuilder::with_auth_manager`].
+pub trait AuthManager: fmt::Debug + Send + Sync {
+    /// Returns the token that connections should authenticate with.
+    fn get_auth(&self) -> BoxFuture<'_, Result<AuthToken>>;
+
+    /// Called when the server rejected the work of a connection with a security error,
+    /// e.g. because the token has expired.
+    ///
+    /// Returns `true` if the manager will provide a new token
+    /// and the failed work should be retried with it.
+    fn handle_security_error(&self, error: &Neo4jError) -> bool;
+}
+
+type RefreshFn = dyn Fn() -> BoxFuture<'static, Result<(AuthToken, Option<Instant>)>> + Send + Sync;
+
+/// Caches a token until it expires and then fetches a new one,
+/// e.g. from an identity provider.
+///
+/// The refresh function returns the token together with the instant it expires at,
+/// or `None` if it only expires when the server reports so with a `TokenExpired` error.
+///
+/// ```no_run
+/// use std::time::{Duration, Instant};
+/// use futures::FutureExt as _;
+/// use neo4rs::{AuthToken, ExpiringAuthManager};
+///
+/// let manager = ExpiringAuthManager::new(|| {
+///     async {
+///         // fetch a new token from the identity provider
+///         let token = AuthToken::bearer("eyJhbGciOiJIUzI1NiJ9...");
+///         Ok((token, Some(Instant::now() + Duration::from_secs(3600))))
+///     }
+///     .boxed()
+/// });
+/// ```
+pub struct ExpiringAuthManager {
+    refresh: Box<RefreshFn>,
+    current: Mutex<Option<(AuthToken, Option<Instant>)>>,
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+impl ExpiringAuthManager {
+    pub fn new<F>(refresh: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, Result<(AuthToken, Option<Instant>)>> + Send + Sync + 'static,
+    {
+        Self {
+            refresh: Box::new(refresh),
+            current: Mutex::new(None),
+            refreshing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn cached(&self) -> Option<AuthToken> {
+        let current = self.current.lock().unwrap();
+        match &*current {
+            Some((token, expires_at)) if expires_at.map_or(true, |at| Instant::now() < at) => {
+                Some(token.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl AuthManager for ExpiringAuthManager {
+    fn get_auth(&self) -> BoxFuture<'_, Result<AuthToken>> {
+        Box::pin(async move {
+            if let Some(token) = self.cached() {
+                return Ok(token);
+            }
+            let _refreshing = self.refreshing.lock().await;
+            // another task might have refreshed the token while we were waiting for the lock
+            if let Some(token) = self.cached() {
+                return Ok(token);
+            }
+            let (token, expires_at) = (self.refresh)().await?;
+            *self.current.lock().unwrap() = Some((token.clone(), expires_at));
+            Ok(token)
+        })
+    }
+
+    fn handle_security_error(&self, error: &Neo4jError) -> bool {
+        if error.kind()
+            == Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(
+                Neo4jSecurityErrorKind::TokenExpired,
+            ))
+        {
+            *self.current.lock().unwrap() = None;
+            return true;
+        }
+        false
+    }
+}
+
+impl fmt::Debug for ExpiringAuthManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpiringAuthManager")
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Always authenticates with the same token, as configured with [`crate::ConfigBuilder::with_auth`].
+#[derive(Debug)]
+pub(crate) struct StaticAuthManager {
+    token: AuthToken,
+}
+
+impl StaticAuthManager {
+    pub(crate) fn new(token: AuthToken) -> Self {
+        Self { token }
+    }
+}
+
+impl AuthManager for StaticAuthManager {
+    fn get_auth(&self) -> BoxFuture<'_, Result<AuthToken>> {
+        Box::pin(futures::future::ready(Ok(self.token.clone())))
+    }
+
+    fn handle_security_error(&self, _error: &Neo4jError) -> bool {
+        false
+    }
+}
+
+/// The credentials a connection is authenticated with.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Credentials {
+    pub(crate) token: AuthToken,
+    /// `None` for the credentials of a session, which never match those of the pool,
+    /// only those of another session with the same token.
+    generation: Option<usize>,
+}
+
+impl Credentials {
+    pub(crate) fn session(token: AuthToken) -> Self {
+        Self {
+            token,
+            generation: None,
+        }
+    }
+}
+
+/// Shares the auth manager between all connections of a pool.
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectionAuth {
+    manager: Arc<dyn AuthManager>,
+    /// Incremented whenever the authorization of the connections has expired,
+    /// so that they authenticate again even if the token did not change.
+    generation: Arc<AtomicUsize>,
+}
+
+impl ConnectionAuth {
+    pub(crate) fn new(manager: Arc<dyn AuthManager>) -> Self {
+        Self {
+            manager,
+            generation: Arc::default(),
+        }
+    }
+
+    /// The credentials that connections should be authenticated with right now.
+    pub(crate) async fn current(&self) -> Result<Credentials> {
+        let generation = self.generation.load(Ordering::Acquire);
+        let token = self.manager.get_auth().await?;
+        Ok(Credentials {
+            token,
+            generation: Some(generation),
+        })
+    }
+
+    /// Returns `true` if work that failed with the error should be retried,
+    /// because the connections will authenticate again before they are used.
+    pub(crate) fn on_error(&self, error: &Neo4jError) -> bool {
+        match error.kind() {
+            Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(
+                Neo4jSecurityErrorKind::AuthorizationExpired,
+            )) => {
+                self.generation.fetch_add(1, Ordering::AcqRel);
+                self.manager.handle_security_error(error);
+                true
+            }
+            Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(_)) => {
+                self.manager.handle_security_error(error)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::AtomicU32, time::Duration};
+
+    use futures::FutureExt as _;
+
+    use super::*;
+
+    fn security_error(code: &str) -> Neo4jError {
+        Neo4jError::new(format!("Neo.ClientError.Security.{code}"), String::new())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_expired_tokens() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let manager = ExpiringAuthManager::new({
+            let calls = calls.clone();
+            move || {
+                let n = calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let expires_at = (n == 0).then(|| Instant::now() - Duration::from_secs(1));
+                    Ok((AuthToken::bearer(format!("token{n}")), expires_at))
+                }
+                .boxed()
+            }
+        });
+
+        assert_eq!(
+            manager.get_auth().await.unwrap(),
+            AuthToken::bearer("token0")
+        );
+        assert_eq!(
+            manager.get_auth().await.unwrap(),
+            AuthToken::bearer("token1")
+        );
+        assert_eq!(
+            manager.get_auth().await.unwrap(),
+            AuthToken::bearer("token1")
+        );
+
+        assert!(!manager.handle_security_error(&security_error("Forbidden")));
+        assert_eq!(
+            manager.get_auth().await.unwrap(),
+            AuthToken::bearer("token1")
+        );
+
+        assert!(manager.handle_security_error(&security_error("TokenExpired")));
+        assert_eq!(
+            manager.get_auth().await.unwrap(),
+            AuthToken::bearer("token2")
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn should_authenticate_again_after_authorization_expired() {
+        let auth = ConnectionAuth::new(Arc::new(StaticAuthManager::new(AuthToken::none())));
+        let before = auth.current().await.unwrap();
+        assert_eq!(auth.current().await.unwrap(), before);
+
+        assert!(!auth.on_error(&security_error("TokenExpired")));
+        assert!(!auth.on_error(&Neo4jError::new(
+            "Neo.TransientError.General.DatabaseUnavailable".into(),
+            String::new()
+        )));
+        assert_eq!(auth.current().await.unwrap(), before);
+
+        assert!(auth.on_error(&security_error("AuthorizationExpired")));
+        let after = auth.current().await.unwrap();
+        assert_eq!(after.token, before.token);
+        assert_ne!(after, before);
+    }
+
+    #[tokio::test]
+    async fn should_not_mistake_session_credentials_for_those_of_the_pool() {
+        let auth = ConnectionAuth::new(Arc::new(StaticAuthManager::new(AuthToken::none())));
+        let pool = auth.current().await.unwrap();
+        assert_ne!(Credentials::session(AuthToken::none()), pool);
+    }
+}
@@ -1,4 +1,20 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+use crate::{
+    errors::{Error, Result},
+    types::{BoltMap, BoltType},
+};
+
+mod manager;
+
+pub use manager::{AuthManager, ExpiringAuthManager};
+pub(crate) use manager::{ConnectionAuth, Credentials, StaticAuthManager};
 
 #[derive(Debug, Clone)]
 pub struct ClientCertificate {
@@ -12,3 +28,280 @@ impl ClientCertificate {
         }
     }
 }
+
+/// The certificate chain and private key that the driver presents to the server
+/// for mutual TLS, see [`crate::ConfigBuilder::with_client_identity`].
+///
+/// Both are read from PEM, the private key can be in PKCS#8, PKCS#1 (RSA) or SEC1 (EC) format.
+///
+/// ```
+/// use neo4rs::ClientIdentity;
+///
+/// let identity = ClientIdentity::from_files("client.pem", "client.key");
+/// ```
+#[derive(Clone)]
+pub struct ClientIdentity {
+    cert_chain: Pem,
+    private_key: Pem,
+}
+
+#[derive(Clone)]
+enum Pem {
+    File(PathBuf),
+    Bytes(Arc<[u8]>),
+}
+
+impl ClientIdentity {
+    /// Reads the certificate chain and the private key from PEM files
+    /// once the graph is created, see [`crate::ConfigBuilder::with_client_identity`].
+    pub fn from_files(cert_chain: impl AsRef<Path>, private_key: impl AsRef<Path>) -> Self {
+        Self {
+            cert_chain: Pem::File(cert_chain.as_ref().to_path_buf()),
+            private_key: Pem::File(private_key.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Uses a certificate chain and a private key that are already in memory.
+    pub fn from_pem(cert_chain: impl Into<Vec<u8>>, private_key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            cert_chain: Pem::Bytes(cert_chain.into().into()),
+            private_key: Pem::Bytes(private_key.into().into()),
+        }
+    }
+
+    pub(crate) fn cert_chain(&self) -> Result<Vec<CertificateDer<'static>>> {
+        let pem = self.cert_chain.read("certificate chain")?;
+        let certs = rustls_pemfile::certs(&mut &*pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                Error::InvalidClientIdentity(format!(
+                    "cannot parse certificate chain from {}: {e}",
+                    self.cert_chain
+                ))
+            })?;
+        if certs.is_empty() {
+            return Err(Error::InvalidClientIdentity(format!(
+                "no certificate found in {}",
+                self.cert_chain
+            )));
+        }
+        Ok(certs)
+    }
+
+    pub(crate) fn private_key(&self) -> Result<PrivateKeyDer<'static>> {
+        let pem = self.private_key.read("private key")?;
+        rustls_pemfile::private_key(&mut &*pem)
+            .map_err(|e| {
+                Error::InvalidClientIdentity(format!(
+                    "cannot parse private key from {}: {e}",
+                    self.private_key
+                ))
+            })?
+            .ok_or_else(|| {
+                Error::InvalidClientIdentity(format!(
+                    "no PKCS#8, RSA or EC private key found in {}",
+                    self.private_key
+                ))
+            })
+    }
+}
+
+impl Pem {
+    fn read(&self, what: &str) -> Result<Arc<[u8]>> {
+        match self {
+            Pem::File(path) => fs::read(path).map(Into::into).map_err(|e| {
+                Error::InvalidClientIdentity(format!(
+                    "cannot read {what} from {}: {e}",
+                    path.display()
+                ))
+            }),
+            Pem::Bytes(bytes) => Ok(bytes.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Pem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pem::File(path) => write!(f, "{}", path.display()),
+            Pem::Bytes(_) => f.write_str("the PEM data"),
+        }
+    }
+}
+
+impl fmt::Debug for ClientIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientIdentity")
+            .field("cert_chain", &self.cert_chain.to_string())
+            .field("private_key", &self.private_key.to_string())
+            .finish()
+    }
+}
+
+/// The credentials that are used to authenticate with the server,
+/// see [`crate::ConfigBuilder::with_auth`].
+///
+/// ```
+/// use neo4rs::AuthToken;
+///
+/// let auth = AuthToken::basic("neo4j", "password");
+/// let auth = AuthToken::bearer("eyJhbGciOiJIUzI1NiJ9...");
+/// let auth = AuthToken::custom("acme", "jane", "s3cret")
+///     .with_realm("acme-realm")
+///     .with_parameter("tenant", 42);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct AuthToken {
+    scheme: String,
+    principal: Option<String>,
+    credentials: Option<String>,
+    realm: Option<String>,
+    parameters: BoltMap,
+}
+
+impl AuthToken {
+    /// Authenticates with a username and password.
+    pub fn basic(user: impl Into<String>, password: impl Into<String>) -> Self {
+        Self::new("basic", Some(user.into()), Some(password.into()))
+    }
+
+    /// Authenticates with a single sign-on token, e.g. a JWT issued by an identity provider.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self::new("bearer", None, Some(token.into()))
+    }
+
+    /// Authenticates with a base64 encoded kerberos ticket.
+    pub fn kerberos(ticket: impl Into<String>) -> Self {
+        Self::new("kerberos", Some(String::new()), Some(ticket.into()))
+    }
+
+    /// Does not authenticate at all, which only works if authentication is disabled on the server.
+    pub fn none() -> Self {
+        Self::new("none", None, None)
+    }
+
+    /// Authenticates with a scheme that is implemented by a server plugin.
+    pub fn custom(
+        scheme: impl Into<String>,
+        principal: impl Into<String>,
+        credentials: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            scheme.into(),
+            Some(principal.into()),
+            Some(credentials.into()),
+        )
+    }
+
+    fn new(
+        scheme: impl Into<String>,
+        principal: Option<String>,
+        credentials: Option<String>,
+    ) -> Self {
+        Self {
+            scheme: scheme.into(),
+            principal,
+            credentials,
+            realm: None,
+            parameters: BoltMap::default(),
+        }
+    }
+
+    /// The realm, i.e. the auth provider on the server that should handle the credentials.
+    pub fn with_realm(mut self, realm: impl Into<String>) -> Self {
+        self.realm = Some(realm.into());
+        self
+    }
+
+    /// An additional parameter for a custom scheme.
+    pub fn with_parameter<T: Into<BoltType>>(mut self, key: &str, value: T) -> Self {
+        self.parameters.put(key.into(), value.into());
+        self
+    }
+
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    pub fn principal(&self) -> Option<&str> {
+        self.principal.as_deref()
+    }
+
+    /// The entries that are sent in HELLO or LOGON.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (&'static str, BoltType)> + '_ {
+        let strings = [
+            ("scheme", Some(&self.scheme)),
+            ("principal", self.principal.as_ref()),
+            ("credentials", self.credentials.as_ref()),
+            (
+                "realm",
+                self.realm.as_ref().filter(|realm| !realm.is_empty()),
+            ),
+        ];
+        let parameters = (!self.parameters.value.is_empty())
+            .then(|| ("parameters", BoltType::Map(self.parameters.clone())));
+        strings
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, BoltType::from(value?.as_str()))))
+            .chain(parameters)
+    }
+
+    pub(crate) fn to_bolt(&self) -> BoltMap {
+        self.entries().map(|(k, v)| (k.into(), v)).collect()
+    }
+}
+
+impl fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthToken")
+            .field("scheme", &self.scheme)
+            .field("principal", &self.principal)
+            .field("credentials", &self.credentials.as_ref().map(|_| "*****"))
+            .field("realm", &self.realm)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_build_auth_tokens() {
+        let auth = AuthToken::basic("neo4j", "pass").to_bolt();
+        assert_eq!(auth.get::<String>("scheme").unwrap(), "basic");
+        assert_eq!(auth.get::<String>("principal").unwrap(), "neo4j");
+        assert_eq!(auth.get::<String>("credentials").unwrap(), "pass");
+        assert_eq!(auth.value.len(), 3);
+
+        let auth = AuthToken::bearer("token").to_bolt();
+        assert_eq!(auth.get::<String>("scheme").unwrap(), "bearer");
+        assert_eq!(auth.get::<String>("credentials").unwrap(), "token");
+        assert_eq!(auth.value.len(), 2);
+
+        let auth = AuthToken::kerberos("ticket").to_bolt();
+        assert_eq!(auth.get::<String>("scheme").unwrap(), "kerberos");
+        assert_eq!(auth.get::<String>("principal").unwrap(), "");
+        assert_eq!(auth.get::<String>("credentials").unwrap(), "ticket");
+
+        let auth = AuthToken::none().to_bolt();
+        assert_eq!(auth.get::<String>("scheme").unwrap(), "none");
+        assert_eq!(auth.value.len(), 1);
+
+        let auth = AuthToken::custom("acme", "jane", "secret")
+            .with_realm("acme-realm")
+            .with_parameter("tenant", 42)
+            .to_bolt();
+        assert_eq!(auth.get::<String>("scheme").unwrap(), "acme");
+        assert_eq!(auth.get::<String>("realm").unwrap(), "acme-realm");
+        let parameters = auth.get::<BoltMap>("parameters").unwrap();
+        assert_eq!(parameters.get::<i64>("tenant").unwrap(), 42);
+    }
+
+    #[test]
+    fn should_not_leak_credentials_in_debug_output() {
+        let debug = format!("{:?}", AuthToken::basic("neo4j", "s3cret"));
+        assert!(debug.contains("neo4j"));
+        assert!(!debug.contains("s3cret"));
+    }
+}
@@ -13,7 +13,7 @@ mod request;
 mod structs;
 mod summary;
 
-pub use request::{Commit, Discard, Goodbye, Hello, Reset, Rollback, WrapExtra};
+pub use request::{Begin, Commit, Discard, Goodbye, Hello, Reset, Rollback, Run, WrapExtra};
 pub use structs::{
     Bolt, BoltRef, Date, DateDuration, DateTime, DateTimeZoneId, DateTimeZoneIdRef, Duration,
     LegacyDateTime, LegacyDateTimeZoneId, LegacyDateTimeZoneIdRef, LocalDateTime, LocalTime, Node,
@@ -60,7 +60,7 @@ pub(crate) trait ExpectedResponse {
     type Response: MessageResponse;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum Response<R, S> {
     Detail(R),
     Success(S),
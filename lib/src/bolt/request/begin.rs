@@ -0,0 +1,197 @@
+use serde::{ser::SerializeMap as _, Serialize};
+
+use crate::{
+    bolt::{ExpectedResponse, Summary},
+    config::{AccessMode, TxConfig},
+    BoltType, Version,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Begin<'a> {
+    extra: TxMeta<'a>,
+}
+
+impl<'a> Begin<'a> {
+    pub fn new(db: Option<&'a str>, bookmarks: &'a [String], config: &'a TxConfig) -> Self {
+        Begin {
+            extra: TxMeta {
+                db,
+                bookmarks,
+                config: Some(config),
+                version: None,
+            },
+        }
+    }
+
+    /// The Bolt version of the connection, which decides how the notification filter
+    /// of the [`TxConfig`] is sent.
+    pub fn version(mut self, version: Version) -> Self {
+        self.extra.version = Some(version);
+        self
+    }
+}
+
+impl<'a> Serialize for Begin<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_variant("Request", 0x11, "BEGIN", &self.extra)
+    }
+}
+
+impl<'a> ExpectedResponse for Begin<'a> {
+    type Response = Summary<()>;
+}
+
+/// The extra map of BEGIN and of RUN in an auto-commit transaction.
+/// Only the fields that differ from the server defaults are sent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TxMeta<'a> {
+    pub(crate) db: Option<&'a str>,
+    pub(crate) bookmarks: &'a [String],
+    pub(crate) config: Option<&'a TxConfig>,
+    /// Without a version, the notification filter is sent as the newest version expects it.
+    pub(crate) version: Option<Version>,
+}
+
+impl<'a> TxMeta<'a> {
+    fn timeout(&self) -> Option<i64> {
+        self.config.and_then(TxConfig::timeout_millis)
+    }
+
+    fn metadata(&self) -> Option<&'a crate::BoltMap> {
+        self.config
+            .map(|config| &config.metadata)
+            .filter(|metadata| !metadata.value.is_empty())
+    }
+
+    fn read(&self) -> bool {
+        self.config
+            .is_some_and(|config| config.mode() == AccessMode::Read)
+    }
+
+    fn imp_user(&self) -> Option<&'a str> {
+        self.config.and_then(|config| config.imp_user.as_deref())
+    }
+
+    fn notification_filter(&self) -> Vec<(&'static str, BoltType)> {
+        self.config
+            .and_then(|config| config.notification_filter.as_ref())
+            .map(|filter| filter.entries(self.version.unwrap_or(Version::V5_7)))
+            .unwrap_or_default()
+    }
+}
+
+impl<'a> Serialize for TxMeta<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let timeout = self.timeout();
+        let metadata = self.metadata();
+        let read = self.read();
+        let imp_user = self.imp_user();
+        let notification_filter = self.notification_filter();
+
+        let len = usize::from(self.db.is_some())
+            + usize::from(!self.bookmarks.is_empty())
+            + usize::from(timeout.is_some())
+            + usize::from(metadata.is_some())
+            + usize::from(read)
+            + usize::from(imp_user.is_some())
+            + notification_filter.len();
+
+        let mut ser = serializer.serialize_map(Some(len))?;
+        if let Some(db) = self.db {
+            ser.serialize_entry("db", db)?;
+        }
+        if !self.bookmarks.is_empty() {
+            ser.serialize_entry("bookmarks", self.bookmarks)?;
+        }
+        if let Some(timeout) = timeout {
+            ser.serialize_entry("tx_timeout", &timeout)?;
+        }
+        if let Some(metadata) = metadata {
+            ser.serialize_entry("tx_metadata", &crate::BoltType::Map(metadata.clone()))?;
+        }
+        if read {
+            ser.serialize_entry("mode", "r")?;
+        }
+        if let Some(imp_user) = imp_user {
+            ser.serialize_entry("imp_user", imp_user)?;
+        }
+        for (key, value) in &notification_filter {
+            ser.serialize_entry(key, value)?;
+        }
+        ser.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{bolt::Message as _, packstream::bolt, NotificationCategory, NotificationFilter};
+
+    #[test]
+    fn serialize() {
+        let config = TxConfig::new()
+            .timeout(Duration::from_secs(2))
+            .metadata("app", "test")
+            .access_mode(AccessMode::Read);
+        let bookmarks = ["bm:1".to_owned()];
+        let begin = Begin::new(Some("neo4j"), &bookmarks, &config);
+        let bytes = begin.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x11)
+            .tiny_map(5)
+            .tiny_string("db")
+            .tiny_string("neo4j")
+            .tiny_string("bookmarks")
+            .tiny_list(1)
+            .tiny_string("bm:1")
+            .tiny_string("tx_timeout")
+            .int16(2000)
+            .tiny_string("tx_metadata")
+            .tiny_map(1)
+            .tiny_string("app")
+            .tiny_string("test")
+            .tiny_string("mode")
+            .tiny_string("r")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_defaults() {
+        let config = TxConfig::default();
+        let begin = Begin::new(None, &[], &config);
+        let bytes = begin.to_bytes().unwrap();
+
+        let expected = bolt().structure(1, 0x11).tiny_map(0).build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_notification_filter() {
+        let filter = NotificationFilter::new().disable_categories([NotificationCategory::Hint]);
+        let config = TxConfig::new().notification_filter(filter);
+        let begin = Begin::new(None, &[], &config).version(Version::V5_4);
+        let bytes = begin.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x11)
+            .tiny_map(1)
+            .string8("notifications_disabled_categories")
+            .tiny_list(1)
+            .tiny_string("HINT")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+}
@@ -1,29 +1,70 @@
-use crate::bolt::{ExpectedResponse, Summary};
-use serde::{Deserialize, Serialize};
+use crate::{
+    auth::AuthToken,
+    bolt::{ExpectedResponse, Summary},
+    notification::NotificationFilter,
+    Version,
+};
+use serde::{ser::SerializeMap as _, Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Hello<'a> {
     metadata: Meta<'a>,
 }
 
 impl<'a> Hello<'a> {
-    pub fn new(user_agent: &'a str, principal: &'a str, credentials: &'a str) -> Self {
+    pub fn new(user_agent: &'a str, auth: &'a AuthToken) -> Self {
         let metadata = Meta {
             user_agent,
-            scheme: "basic",
-            principal,
-            credentials,
+            auth,
+            notification_filter: None,
+            version: None,
         };
         Hello { metadata }
     }
+
+    /// Filters the notifications of all queries on the connection, which requires Bolt 5.2 or later.
+    pub fn notification_filter(mut self, filter: &'a NotificationFilter) -> Self {
+        self.metadata.notification_filter = Some(filter);
+        self
+    }
+
+    /// The Bolt version of the connection, which decides how the notification filter is sent.
+    pub fn version(mut self, version: Version) -> Self {
+        self.metadata.version = Some(version);
+        self
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq)]
 struct Meta<'a> {
-    scheme: &'a str,
-    principal: &'a str,
-    credentials: &'a str,
     user_agent: &'a str,
+    auth: &'a AuthToken,
+    notification_filter: Option<&'a NotificationFilter>,
+    /// Without a version, the notification filter is sent as the newest version expects it.
+    version: Option<Version>,
+}
+
+impl<'a> Serialize for Meta<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let entries = self.auth.entries().collect::<Vec<_>>();
+        let notification_filter = self
+            .notification_filter
+            .map(|filter| filter.entries(self.version.unwrap_or(Version::V5_7)))
+            .unwrap_or_default();
+        let mut ser =
+            serializer.serialize_map(Some(entries.len() + 1 + notification_filter.len()))?;
+        for (key, value) in &entries {
+            ser.serialize_entry(key, value)?;
+        }
+        ser.serialize_entry("user_agent", self.user_agent)?;
+        for (key, value) in &notification_filter {
+            ser.serialize_entry(key, value)?;
+        }
+        ser.end()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
@@ -51,11 +92,13 @@ mod tests {
     use crate::{
         bolt::{Message as _, MessageResponse as _},
         packstream::bolt,
+        NotificationSeverity,
     };
 
     #[test]
     fn serialize() {
-        let hello = Hello::new("foo", "user", "pass");
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::new("foo", &auth);
         let bytes = hello.to_bytes().unwrap();
 
         let expected = bolt()
@@ -74,6 +117,63 @@ mod tests {
         assert_eq!(bytes, expected);
     }
 
+    #[test]
+    fn serialize_custom_auth() {
+        let auth = AuthToken::custom("acme", "user", "pass")
+            .with_realm("realm")
+            .with_parameter("n", 1);
+        let hello = Hello::new("foo", &auth);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(6)
+            .tiny_string("scheme")
+            .tiny_string("acme")
+            .tiny_string("principal")
+            .tiny_string("user")
+            .tiny_string("credentials")
+            .tiny_string("pass")
+            .tiny_string("realm")
+            .tiny_string("realm")
+            .tiny_string("parameters")
+            .tiny_map(1)
+            .tiny_string("n")
+            .tiny_int(1)
+            .tiny_string("user_agent")
+            .tiny_string("foo")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_notification_filter() {
+        let auth = AuthToken::none();
+        let filter = NotificationFilter::new()
+            .minimum_severity(NotificationSeverity::Warning)
+            .disable_categories([]);
+        let hello = Hello::new("foo", &auth)
+            .notification_filter(&filter)
+            .version(Version::V5_5);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(4)
+            .tiny_string("scheme")
+            .tiny_string("none")
+            .tiny_string("user_agent")
+            .tiny_string("foo")
+            .string8("notifications_minimum_severity")
+            .tiny_string("WARNING")
+            .string8("notifications_disabled_classifications")
+            .tiny_list(0)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
     #[test]
     fn parse() {
         let data = bolt()
@@ -1,3 +1,4 @@
+mod begin;
 mod commit;
 mod discard;
 mod extra;
@@ -6,7 +7,9 @@ mod hello;
 mod pull;
 mod reset;
 mod rollback;
+mod run;
 
+pub use begin::Begin;
 pub use commit::Commit;
 pub use discard::Discard;
 pub use extra::WrapExtra;
@@ -15,3 +18,4 @@ pub use hello::Hello;
 pub use pull::Pull;
 pub use reset::Reset;
 pub use rollback::Rollback;
+pub use run::Run;
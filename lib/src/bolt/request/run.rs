@@ -0,0 +1,153 @@
+use serde::{ser::SerializeTupleVariant as _, Deserialize, Serialize};
+
+use crate::{
+    bolt::{request::begin::TxMeta, ExpectedResponse, Summary},
+    config::TxConfig,
+    Version,
+};
+
+/// Runs a query, either in an explicit transaction or in an auto-commit transaction.
+/// The parameters can be any type that serializes into a map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run<'a, P> {
+    query: &'a str,
+    parameters: P,
+    extra: TxMeta<'a>,
+}
+
+impl<'a, P: Serialize> Run<'a, P> {
+    /// Runs the query in the current explicit transaction.
+    pub fn new(query: &'a str, parameters: P) -> Self {
+        Run {
+            query,
+            parameters,
+            extra: TxMeta::default(),
+        }
+    }
+
+    /// Runs the query in an auto-commit transaction.
+    pub fn auto_commit(
+        query: &'a str,
+        parameters: P,
+        db: Option<&'a str>,
+        bookmarks: &'a [String],
+        config: &'a TxConfig,
+    ) -> Self {
+        Run {
+            query,
+            parameters,
+            extra: TxMeta {
+                db,
+                bookmarks,
+                config: Some(config),
+                version: None,
+            },
+        }
+    }
+
+    /// The Bolt version of the connection, which decides how the notification filter
+    /// of the [`TxConfig`] is sent.
+    pub fn version(mut self, version: Version) -> Self {
+        self.extra.version = Some(version);
+        self
+    }
+}
+
+impl<'a, P: Serialize> Serialize for Run<'a, P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ser = serializer.serialize_tuple_variant("Request", 0x10, "RUN", 3)?;
+        ser.serialize_field(self.query)?;
+        ser.serialize_field(&self.parameters)?;
+        ser.serialize_field(&self.extra)?;
+        ser.end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Response {
+    pub(crate) fields: Vec<String>,
+    #[serde(default)]
+    pub(crate) qid: Option<i64>,
+}
+
+impl<'a, P> ExpectedResponse for Run<'a, P> {
+    type Response = Summary<Response>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        bolt::{Message as _, MessageResponse as _},
+        config::AccessMode,
+        packstream::bolt,
+    };
+
+    #[test]
+    fn serialize() {
+        let params = BTreeMap::from([("age", 42)]);
+        let run = Run::new("RETURN $age", params);
+        let bytes = run.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(3, 0x10)
+            .tiny_string("RETURN $age")
+            .tiny_map(1)
+            .tiny_string("age")
+            .tiny_int(42)
+            .tiny_map(0)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_auto_commit() {
+        let config = TxConfig::new().access_mode(AccessMode::Read);
+        let run = Run::auto_commit(
+            "RETURN 1",
+            BTreeMap::<&str, i64>::new(),
+            Some("neo4j"),
+            &[],
+            &config,
+        );
+        let bytes = run.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(3, 0x10)
+            .tiny_string("RETURN 1")
+            .tiny_map(0)
+            .tiny_map(2)
+            .tiny_string("db")
+            .tiny_string("neo4j")
+            .tiny_string("mode")
+            .tiny_string("r")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn parse() {
+        let data = bolt()
+            .tiny_map(3)
+            .tiny_string("t_first")
+            .tiny_int(1)
+            .tiny_string("fields")
+            .tiny_list(1)
+            .tiny_string("n")
+            .tiny_string("qid")
+            .tiny_int(7)
+            .build();
+
+        let response = Response::parse(data).unwrap();
+
+        assert_eq!(response.fields, ["n"]);
+        assert_eq!(response.qid, Some(7));
+    }
+}
@@ -295,12 +295,15 @@ impl From<Bolt> for crate::BoltType {
         fn conv_unrel(rel: urel::UnboundRelationship) -> BoltUnboundedRelation {
             let id = BoltInteger::new(rel.id().try_into().unwrap());
             let typ = BoltString::from(rel.typ());
+            let element_id = rel.element_id().map(BoltString::from);
             let properties = rel.into::<Bolt>().unwrap();
             let properties = BoltType::from(properties);
             let BoltType::Map(properties) = properties else {
                 panic!("properties should be a map");
             };
-            BoltUnboundedRelation::new(id, typ, properties)
+            let mut rel = BoltUnboundedRelation::new(id, typ, properties);
+            rel.element_id = element_id;
+            rel
         }
 
         match value {
@@ -320,6 +323,7 @@ impl From<Bolt> for crate::BoltType {
             ),
             Bolt::Node(v) => {
                 let id = v.id();
+                let element_id = v.element_id().map(BoltString::from);
                 let labels = v
                     .labels()
                     .iter()
@@ -331,17 +335,22 @@ impl From<Bolt> for crate::BoltType {
                 let BoltType::Map(properties) = properties else {
                     panic!("properties should be a map");
                 };
-                Self::Node(BoltNode::new(
+                let mut node = BoltNode::new(
                     BoltInteger::new(id.try_into().unwrap()),
                     BoltList::from(labels),
                     properties,
-                ))
+                );
+                node.element_id = element_id;
+                Self::Node(node)
             }
             Bolt::Relationship(v) => {
                 let id = v.id();
                 let start_node_id = v.start_node_id();
                 let end_node_id = v.end_node_id();
                 let typ = BoltString::from(v.typ());
+                let element_id = v.element_id().map(BoltString::from);
+                let start_node_element_id = v.start_node_element_id().map(BoltString::from);
+                let end_node_element_id = v.end_node_element_id().map(BoltString::from);
                 let properties = v.into::<Bolt>().unwrap();
                 let properties = BoltType::from(properties);
                 let BoltType::Map(properties) = properties else {
@@ -353,6 +362,9 @@ impl From<Bolt> for crate::BoltType {
                     end_node_id: BoltInteger::new(end_node_id.try_into().unwrap()),
                     typ,
                     properties,
+                    element_id,
+                    start_node_element_id,
+                    end_node_element_id,
                 })
             }
             Bolt::Path(v) => {
@@ -395,7 +407,7 @@ impl From<Bolt> for crate::BoltType {
                 nanoseconds: BoltInteger::new(v.nanoseconds_since_midnight().try_into().unwrap()),
             }),
             Bolt::DateTime(v) => Self::DateTime(BoltDateTime {
-                seconds: v.seconds_since_epoch().into(),
+                seconds: (v.seconds_since_epoch() + i64::from(v.timezone_offset_seconds())).into(),
                 nanoseconds: BoltInteger::new(v.nanoseconds().into()),
                 tz_offset_seconds: v.timezone_offset_seconds().into(),
             }),
@@ -403,6 +415,7 @@ impl From<Bolt> for crate::BoltType {
                 seconds: v.seconds_since_epoch().into(),
                 nanoseconds: BoltInteger::new(v.nanoseconds().into()),
                 tz_id: v.timezone_identifier().into(),
+                utc: true,
             }),
             Bolt::LocalDateTime(v) => Self::LocalDateTime(BoltLocalDateTime {
                 seconds: v.seconds_since_epoch().into(),
@@ -434,6 +447,7 @@ impl From<Bolt> for crate::BoltType {
                 seconds: v.seconds_since_epoch().into(),
                 nanoseconds: BoltInteger::new(v.nanoseconds().into()),
                 tz_id: v.timezone_identifier().into(),
+                utc: false,
             }),
         }
     }
@@ -5,7 +5,9 @@ use serde::{
     Deserialize,
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use crate::{errors::Neo4jError, BoltMap};
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Summary<R> {
     Success(Success<R>),
     Ignored,
@@ -31,17 +33,44 @@ pub struct Success<R> {
     pub(crate) metadata: R,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+/// The code is called `neo4j_code` since Bolt 5.7, which also adds the GQL fields and causes.
+/// Causes have no code.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct Failure {
+    #[serde(default, alias = "neo4j_code")]
     pub(crate) code: String,
     pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) gql_status: Option<String>,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) diagnostic_record: Option<BoltMap>,
+    #[serde(default)]
+    pub(crate) cause: Option<Box<Failure>>,
 }
 
 impl Failure {
     #[allow(unused)]
     pub fn into_error(self, msg: &'static str) -> crate::errors::Error {
-        let Self { code, message } = self;
-        crate::errors::Error::Neo4j(crate::errors::Neo4jError::new(code, message))
+        crate::errors::Error::Neo4j(self.into_neo4j_error())
+    }
+
+    fn into_neo4j_error(self) -> Neo4jError {
+        let Self {
+            code,
+            message,
+            gql_status,
+            description,
+            diagnostic_record,
+            cause,
+        } = self;
+        Neo4jError::new(code, message).with_gql(
+            gql_status,
+            description,
+            diagnostic_record,
+            cause.map(|cause| cause.into_neo4j_error()),
+        )
     }
 }
 
@@ -136,4 +165,52 @@ mod tests {
             "The client is unauthorized due to authentication failure."
         );
     }
+
+    #[test]
+    fn parse_gql_failure() {
+        let data = bolt()
+            .structure(1, 0x7F)
+            .tiny_map(6)
+            .string8("neo4j_code")
+            .string8("Neo.ClientError.Statement.SyntaxError")
+            .tiny_string("message")
+            .tiny_string("Invalid input")
+            .string8("gql_status")
+            .tiny_string("42001")
+            .string8("description")
+            .string8("error: syntax error or access rule violation - invalid syntax")
+            .string8("diagnostic_record")
+            .tiny_map(1)
+            .string8("_classification")
+            .tiny_string("CLIENT_ERROR")
+            .tiny_string("cause")
+            .tiny_map(3)
+            .tiny_string("message")
+            .string8("Invalid input 'X'")
+            .string8("gql_status")
+            .tiny_string("42I06")
+            .string8("description")
+            .string8("error: syntax error or access rule violation - invalid input")
+            .build();
+
+        let failure = match Summary::<()>::parse(data).unwrap() {
+            Summary::Failure(failure) => failure,
+            _ => panic!("Expected failure"),
+        };
+        let crate::Error::Neo4j(error) = failure.into_error("RUN") else {
+            panic!("Expected a Neo4j error");
+        };
+
+        assert_eq!(error.code(), "Neo.ClientError.Statement.SyntaxError");
+        assert_eq!(error.gql_status(), Some("42001"));
+        assert_eq!(
+            error.classification(),
+            crate::GqlErrorClassification::ClientError
+        );
+        let cause = error.cause().unwrap();
+        assert_eq!(cause.code(), "");
+        assert_eq!(cause.gql_status(), Some("42I06"));
+        assert_eq!(cause.message(), "Invalid input 'X'");
+        assert!(cause.cause().is_none());
+    }
 }
@@ -0,0 +1,134 @@
+use std::{
+    collections::BTreeSet,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use crate::{messages::Success, BoltList, BoltType};
+
+/// Keeps track of the bookmarks of completed transactions to establish causal consistency.
+///
+/// Every transaction started by a [`crate::Graph`] waits until the server has caught up
+/// with the bookmarks of the manager, so that it can read the writes of all previous
+/// transactions, even when those ran on a different connection or cluster member.
+///
+/// A [`DefaultBookmarkManager`] is used unless a different one is configured with
+/// [`crate::ConfigBuilder::with_bookmark_manager`].
+/// Sharing a manager between several [`crate::Graph`] instances, or implementing one
+/// that synchronizes bookmarks between services, extends the causal chain across them.
+pub trait BookmarkManager: fmt::Debug + Send + Sync {
+    /// Returns the bookmarks that the next transaction should wait for.
+    fn get_bookmarks(&self) -> Vec<String>;
+
+    /// Called after a transaction completed successfully.
+    /// `previous` are the bookmarks the transaction waited for, which are superseded
+    /// by the `new` bookmarks that it produced.
+    fn update_bookmarks(&self, previous: &[String], new: &[String]);
+}
+
+/// Stores bookmarks in memory.
+#[derive(Debug, Default)]
+pub struct DefaultBookmarkManager {
+    bookmarks: Mutex<BTreeSet<String>>,
+}
+
+impl DefaultBookmarkManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a manager that starts with the given bookmarks,
+    /// e.g. bookmarks that were received from another service.
+    pub fn with_bookmarks(bookmarks: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            bookmarks: Mutex::new(bookmarks.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl BookmarkManager for DefaultBookmarkManager {
+    fn get_bookmarks(&self) -> Vec<String> {
+        self.bookmarks.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn update_bookmarks(&self, previous: &[String], new: &[String]) {
+        if new.is_empty() {
+            return;
+        }
+        let mut bookmarks = self.bookmarks.lock().unwrap();
+        for bookmark in previous {
+            bookmarks.remove(bookmark);
+        }
+        bookmarks.extend(new.iter().cloned());
+    }
+}
+
+/// The bookmarks a single transaction waits for.
+/// Once the transaction completes, its new bookmark is reported back to the manager.
+#[derive(Debug, Clone)]
+pub(crate) struct Bookmarks {
+    manager: Arc<dyn BookmarkManager>,
+    used: Vec<String>,
+}
+
+impl Bookmarks {
+    pub(crate) fn new(manager: Arc<dyn BookmarkManager>) -> Self {
+        let used = manager.get_bookmarks();
+        Self { manager, used }
+    }
+
+    pub(crate) fn used(&self) -> &[String] {
+        &self.used
+    }
+
+    /// Picks up the bookmark from the summary of a COMMIT or of the last
+    /// DISCARD/PULL of an auto-commit transaction.
+    pub(crate) fn update(&self, success: &Success) {
+        if let Ok(bookmark) = success.get::<String>("bookmark") {
+            self.manager.update_bookmarks(&self.used, &[bookmark]);
+        }
+    }
+}
+
+/// Bookmarks are sent as a list of strings in the `bookmarks` field of BEGIN and RUN,
+/// and as the bookmarks of ROUTE.
+pub(crate) fn to_bolt(bookmarks: &[String]) -> BoltList {
+    BoltList::from(
+        bookmarks
+            .iter()
+            .map(|b| BoltType::from(b.as_str()))
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoltMap;
+
+    #[test]
+    fn should_replace_previous_bookmarks() {
+        let manager = DefaultBookmarkManager::with_bookmarks(["bm:1", "bm:2"]);
+        assert_eq!(manager.get_bookmarks(), ["bm:1", "bm:2"]);
+
+        manager.update_bookmarks(&["bm:1".to_owned()], &["bm:3".to_owned()]);
+        assert_eq!(manager.get_bookmarks(), ["bm:2", "bm:3"]);
+
+        manager.update_bookmarks(&["bm:2".to_owned(), "bm:3".to_owned()], &[]);
+        assert_eq!(manager.get_bookmarks(), ["bm:2", "bm:3"]);
+    }
+
+    #[test]
+    fn should_update_from_summary() {
+        let manager = Arc::new(DefaultBookmarkManager::with_bookmarks(["bm:1"]));
+        let bookmarks = Bookmarks::new(manager.clone());
+        assert_eq!(bookmarks.used(), ["bm:1"]);
+
+        bookmarks.update(&Success::new(BoltMap::default()));
+        assert_eq!(manager.get_bookmarks(), ["bm:1"]);
+
+        let metadata = BoltMap::from_entries([("bookmark", "bm:2".into())]);
+        bookmarks.update(&Success::new(metadata));
+        assert_eq!(manager.get_bookmarks(), ["bm:2"]);
+    }
+}
@@ -1,15 +1,71 @@
-use crate::auth::ClientCertificate;
+use crate::auth::{AuthManager, AuthToken, ClientIdentity, StaticAuthManager};
+use crate::bookmarks::{BookmarkManager, DefaultBookmarkManager};
 use crate::errors::{Error, Result};
+use crate::notification::{Notification, NotificationFilter, NotificationHook};
+use crate::tls::TlsConfig;
+use crate::types::{BoltMap, BoltType};
+use crate::Version;
 use std::path::Path;
-use std::{ops::Deref, sync::Arc};
+use std::time::Duration;
+use std::{borrow::Borrow, fmt, ops::Deref, sync::Arc};
 
 const DEFAULT_DATABASE: &str = "neo4j";
 const DEFAULT_FETCH_SIZE: usize = 200;
 const DEFAULT_MAX_CONNECTIONS: usize = 16;
+const DEFAULT_MAX_CONNECTION_LIFETIME: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The size and the lifecycle of the connections of a pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PoolOptions {
+    pub(crate) max_connections: usize,
+    /// The number of connections that are kept open even if they are idle.
+    pub(crate) min_connections: usize,
+    pub(crate) max_lifetime: Option<Duration>,
+    /// Connections that have been idle for longer are checked with a RESET before they are used.
+    pub(crate) liveness_check: Option<Duration>,
+    pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) warm_up: bool,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            min_connections: 0,
+            max_lifetime: Some(DEFAULT_MAX_CONNECTION_LIFETIME),
+            liveness_check: Some(Duration::ZERO),
+            idle_timeout: None,
+            warm_up: false,
+        }
+    }
+}
+
+/// How long the driver waits for the server, `None` waits forever.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Timeouts {
+    pub(crate) connect: Option<Duration>,
+    pub(crate) handshake: Option<Duration>,
+    pub(crate) read: Option<Duration>,
+    pub(crate) acquire: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            connect: Some(DEFAULT_CONNECT_TIMEOUT),
+            handshake: Some(DEFAULT_HANDSHAKE_TIMEOUT),
+            read: None,
+            acquire: Some(DEFAULT_ACQUIRE_TIMEOUT),
+        }
+    }
+}
 
 /// Newtype for the name of the database.
 /// Stores the name as an `Arc<str>` to avoid cloning the name around.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Database(Arc<str>);
 
 impl From<&str> for Database {
@@ -36,6 +92,12 @@ impl AsRef<str> for Database {
     }
 }
 
+impl Borrow<str> for Database {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
 impl Deref for Database {
     type Target = str;
 
@@ -44,23 +106,223 @@ impl Deref for Database {
     }
 }
 
+/// Whether a transaction reads or writes data.
+/// In a cluster, read transactions can be routed to followers and read replicas.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AccessMode {
+    Read,
+    #[default]
+    Write,
+}
+
+impl fmt::Display for AccessMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessMode::Read => f.write_str("read"),
+            AccessMode::Write => f.write_str("write"),
+        }
+    }
+}
+
+/// Configuration for a single transaction, either an explicit one started with
+/// [`crate::Graph::start_txn_with`] or an auto-commit one used by
+/// [`crate::Graph::run_with`] and [`crate::Graph::execute_with`].
+///
+/// ```
+/// use neo4rs::{AccessMode, TxConfig};
+/// use std::time::Duration;
+///
+/// let config = TxConfig::new()
+///     .timeout(Duration::from_secs(5))
+///     .metadata("app", "billing")
+///     .access_mode(AccessMode::Read);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxConfig {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) metadata: BoltMap,
+    pub(crate) access_mode: Option<AccessMode>,
+    pub(crate) imp_user: Option<String>,
+    pub(crate) notification_filter: Option<NotificationFilter>,
+}
+
+impl TxConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The server terminates the transaction once it runs longer than the timeout.
+    /// Without a timeout, the default of the server is used.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Attaches metadata to the transaction, which is shown in `SHOW TRANSACTIONS` and the query log.
+    pub fn metadata<T: Into<BoltType>>(mut self, key: &str, value: T) -> Self {
+        self.metadata.put(key.into(), value.into());
+        self
+    }
+
+    /// Overrides the access mode of the session, which is [`AccessMode::Write`] by default.
+    pub fn access_mode(mut self, access_mode: AccessMode) -> Self {
+        self.access_mode = Some(access_mode);
+        self
+    }
+
+    /// Runs the transaction with the privileges of the given user instead of the
+    /// authenticated one, which requires Bolt 4.4 or later.
+    /// Overrides the user of [`SessionConfig::impersonate`].
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.imp_user = Some(user.into());
+        self
+    }
+
+    /// Filters the notifications of the queries in the transaction, which requires Bolt 5.2 or later.
+    /// Overrides the settings of [`SessionConfig::notification_filter`]
+    /// and of [`ConfigBuilder::with_notification_filter`] that the filter sets,
+    /// the others are kept.
+    pub fn notification_filter(mut self, filter: NotificationFilter) -> Self {
+        self.notification_filter = Some(filter);
+        self
+    }
+
+    /// Fails early if the server cannot handle the configuration,
+    /// instead of silently ignoring parts of it.
+    pub(crate) fn check_version(&self, version: Version) -> Result<()> {
+        if self.imp_user.is_some() && version < Version::V4_4 {
+            return Err(Error::ImpersonationNotSupported(format!(
+                "impersonation requires Bolt 4.4 or later, but the server only supports {version:?}"
+            )));
+        }
+        if let Some(filter) = &self.notification_filter {
+            filter.check_version(version)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn mode(&self) -> AccessMode {
+        self.access_mode.unwrap_or_default()
+    }
+
+    /// The timeout in milliseconds as it is sent to the server.
+    /// Timeouts below a millisecond are rounded up, as 0 would disable the timeout.
+    pub(crate) fn timeout_millis(&self) -> Option<i64> {
+        self.timeout.map(|timeout| {
+            let millis = i64::try_from(timeout.as_millis()).unwrap_or(i64::MAX);
+            if millis == 0 && !timeout.is_zero() {
+                1
+            } else {
+                millis
+            }
+        })
+    }
+}
+
+/// Configuration for a [`crate::Session`], created with [`crate::Graph::session`].
+///
+/// Unless configured otherwise, a session uses the database and fetch size of the [`Config`].
+///
+/// ```
+/// use neo4rs::{AccessMode, SessionConfig};
+///
+/// let config = SessionConfig::new()
+///     .database("tenant42")
+///     .access_mode(AccessMode::Read)
+///     .bookmarks(["FB:kcwQ"])
+///     .fetch_size(500)
+///     .impersonate("jane");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionConfig {
+    pub(crate) db: Option<Database>,
+    pub(crate) access_mode: AccessMode,
+    pub(crate) bookmarks: Vec<String>,
+    pub(crate) fetch_size: Option<usize>,
+    pub(crate) imp_user: Option<String>,
+    pub(crate) auth: Option<AuthToken>,
+    pub(crate) notification_filter: Option<NotificationFilter>,
+}
+
+impl SessionConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The database that all work of the session runs on.
+    /// Defaults to the database of the [`Config`], see [`ConfigBuilder::with_home_database`].
+    pub fn database(mut self, db: impl Into<Database>) -> Self {
+        self.db = Some(db.into());
+        self
+    }
+
+    /// The access mode of queries and transactions that don't specify one in their [`TxConfig`].
+    pub fn access_mode(mut self, access_mode: AccessMode) -> Self {
+        self.access_mode = access_mode;
+        self
+    }
+
+    /// Bookmarks that the first transaction of the session waits for,
+    /// e.g. the [`crate::Session::last_bookmarks`] of another session.
+    pub fn bookmarks(mut self, bookmarks: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.bookmarks = bookmarks.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn fetch_size(mut self, fetch_size: usize) -> Self {
+        self.fetch_size = Some(fetch_size);
+        self
+    }
+
+    /// Runs all work of the session with the privileges of the given user,
+    /// which requires Bolt 4.4 or later.
+    pub fn impersonate(mut self, user: impl Into<String>) -> Self {
+        self.imp_user = Some(user.into());
+        self
+    }
+
+    /// Runs all work of the session as another user, by authenticating pooled connections
+    /// with the given token instead of the credentials of the [`Config`].
+    /// This requires Bolt 5.1 or later.
+    ///
+    /// Unlike [`SessionConfig::impersonate`], this does not require any privileges
+    /// of the user of the [`Config`], but the credentials of the other user.
+    pub fn auth(mut self, auth: AuthToken) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Filters the notifications of all work of the session, which requires Bolt 5.2 or later.
+    /// Overrides the filter of [`ConfigBuilder::with_notification_filter`].
+    pub fn notification_filter(mut self, filter: NotificationFilter) -> Self {
+        self.notification_filter = Some(filter);
+        self
+    }
+}
+
 /// The configuration that is used once a connection is alive.
 #[derive(Debug, Clone)]
 pub struct LiveConfig {
-    pub(crate) db: Database,
+    pub(crate) db: Option<Database>,
     pub(crate) fetch_size: usize,
+    pub(crate) bookmark_manager: Arc<dyn BookmarkManager>,
 }
 
 /// The configuration used to connect to the database, see [`crate::Graph::connect`].
 #[derive(Debug, Clone)]
 pub struct Config {
     pub(crate) uri: String,
-    pub(crate) user: String,
-    pub(crate) password: String,
-    pub(crate) max_connections: usize,
-    pub(crate) db: Database,
+    pub(crate) auth: Arc<dyn AuthManager>,
+    pub(crate) pool: PoolOptions,
+    pub(crate) db: Option<Database>,
     pub(crate) fetch_size: usize,
-    pub(crate) client_certificate: Option<ClientCertificate>,
+    pub(crate) tls: TlsConfig,
+    pub(crate) timeouts: Timeouts,
+    pub(crate) bookmark_manager: Option<Arc<dyn BookmarkManager>>,
+    pub(crate) notifications: NotificationHook,
+    pub(crate) notification_filter: NotificationFilter,
+    #[cfg(feature = "tracing")]
+    pub(crate) trace_query_text: bool,
 }
 
 impl Config {
@@ -68,6 +330,9 @@ impl Config {
         LiveConfig {
             db: self.db,
             fetch_size: self.fetch_size,
+            bookmark_manager: self
+                .bookmark_manager
+                .unwrap_or_else(|| Arc::new(DefaultBookmarkManager::new())),
         }
     }
 }
@@ -77,10 +342,18 @@ pub struct ConfigBuilder {
     uri: Option<String>,
     user: Option<String>,
     password: Option<String>,
-    db: Database,
+    auth: Option<AuthToken>,
+    auth_manager: Option<Arc<dyn AuthManager>>,
+    db: Option<Database>,
     fetch_size: usize,
-    max_connections: usize,
-    client_certificate: Option<ClientCertificate>,
+    pool: PoolOptions,
+    tls: TlsConfig,
+    timeouts: Timeouts,
+    bookmark_manager: Option<Arc<dyn BookmarkManager>>,
+    notifications: NotificationHook,
+    notification_filter: NotificationFilter,
+    #[cfg(feature = "tracing")]
+    trace_query_text: bool,
 }
 
 impl ConfigBuilder {
@@ -107,11 +380,40 @@ impl ConfigBuilder {
         self
     }
 
+    /// The credentials for authenticating with the Neo4j server,
+    /// for schemes other than a username and password.
+    ///
+    /// Takes precedence over [`ConfigBuilder::user`] and [`ConfigBuilder::password`].
+    pub fn with_auth(mut self, auth: AuthToken) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// The manager that provides the credentials for authenticating with the Neo4j server,
+    /// e.g. to refresh tokens that expire.
+    ///
+    /// Takes precedence over [`ConfigBuilder::with_auth`], [`ConfigBuilder::user`]
+    /// and [`ConfigBuilder::password`].
+    pub fn with_auth_manager(mut self, auth_manager: Arc<dyn AuthManager>) -> Self {
+        self.auth_manager = Some(auth_manager);
+        self
+    }
+
     /// The name of the database to connect to.
     ///
     /// Defaults to "neo4j" if not set.
     pub fn db(mut self, db: impl Into<Database>) -> Self {
-        self.db = db.into();
+        self.db = Some(db.into());
+        self
+    }
+
+    /// Lets the server pick the database, which is the home database of the user,
+    /// or the default database of the server if the user has none.
+    ///
+    /// The name of the home database is resolved once and cached per user.
+    /// It is resolved again when the server reports that the database does not exist anymore.
+    pub fn with_home_database(mut self) -> Self {
+        self.db = None;
         self
     }
 
@@ -128,25 +430,216 @@ impl ConfigBuilder {
     ///
     /// Defaults to 16 if not set.
     pub fn max_connections(mut self, max_connections: usize) -> Self {
-        self.max_connections = max_connections;
+        self.pool.max_connections = max_connections;
         self
     }
 
+    /// The number of idle connections that are kept open when idle connections are closed
+    /// after the [`ConfigBuilder::idle_timeout`], and that are opened by [`ConfigBuilder::warm_up`].
+    ///
+    /// Defaults to 0 if not set.
+    pub fn min_connections(mut self, min_connections: usize) -> Self {
+        self.pool.min_connections = min_connections;
+        self
+    }
+
+    /// Connections that have been open for longer are closed instead of being reused,
+    /// e.g. before a load balancer or firewall drops them.
+    ///
+    /// Defaults to 1 hour, `None` keeps connections open for as long as they work.
+    pub fn max_connection_lifetime(mut self, lifetime: impl Into<Option<Duration>>) -> Self {
+        self.pool.max_lifetime = lifetime.into();
+        self
+    }
+
+    /// Connections that have been idle for longer are checked with a round trip to the server
+    /// before they are handed out, and replaced if the server does not answer.
+    ///
+    /// Connections that are not in a clean state are always reset before they are reused.
+    /// Defaults to zero, which checks every connection, `None` disables the check.
+    pub fn idle_time_before_liveness_check(
+        mut self,
+        idle_time: impl Into<Option<Duration>>,
+    ) -> Self {
+        self.pool.liveness_check = idle_time.into();
+        self
+    }
+
+    /// Closes connections that have been idle for longer,
+    /// as long as more than [`ConfigBuilder::min_connections`] connections are open.
+    ///
+    /// Defaults to `None`, which keeps idle connections open.
+    pub fn idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.pool.idle_timeout = timeout.into();
+        self
+    }
+
+    /// Opens [`ConfigBuilder::min_connections`] connections in [`crate::Graph::connect`],
+    /// which fails if the server cannot be reached.
+    ///
+    /// Defaults to `false`, which opens connections when they are needed.
+    pub fn warm_up(mut self, warm_up: bool) -> Self {
+        self.pool.warm_up = warm_up;
+        self
+    }
+
+    /// Trusts the CA certificates in the given PEM file for the `+s` schemes,
+    /// in addition to the native roots.
     pub fn with_client_certificate(mut self, client_cert: impl AsRef<Path>) -> Self {
-        self.client_certificate = Some(ClientCertificate::new(client_cert));
+        self.tls = self.tls.with_ca_certificates_file(client_cert);
+        self
+    }
+
+    /// The TLS settings for the encrypted schemes, e.g. which CA certificates are trusted,
+    /// the minimum TLS version or a fully custom `rustls` config.
+    ///
+    /// Replaces all TLS settings made so far,
+    /// including those of [`ConfigBuilder::with_client_certificate`],
+    /// [`ConfigBuilder::with_client_identity`] and [`ConfigBuilder::with_certificate_fingerprint`].
+    pub fn with_tls_config(mut self, tls: TlsConfig) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// The certificate chain and private key that are presented to the server
+    /// for mutual TLS with an encrypted scheme like `neo4j+s://`.
+    ///
+    /// The identity is read once when the connection pool is created, so
+    /// [`crate::Graph::new`] and [`crate::Graph::connect`] fail with
+    /// [`Error::InvalidClientIdentity`] if it cannot be read or the key does not match.
+    pub fn with_client_identity(mut self, identity: ClientIdentity) -> Self {
+        self.tls = self.tls.with_client_identity(identity);
+        self
+    }
+
+    /// Pins the certificate of the server to the given SHA-256 fingerprint of its DER encoding,
+    /// as printed by `openssl x509 -in cert.pem -outform der | sha256sum`.
+    ///
+    /// Only applies to the `bolt+ssc://` and `neo4j+ssc://` schemes, which otherwise accept
+    /// any certificate, including self-signed ones.
+    pub fn with_certificate_fingerprint(mut self, sha256: [u8; 32]) -> Self {
+        self.tls = self.tls.with_certificate_fingerprint(sha256);
+        self
+    }
+
+    /// How long to wait for the TCP connection to a server to be established,
+    /// which fails with [`Error::ConnectTimeout`] otherwise.
+    ///
+    /// Defaults to 30 seconds, `None` waits until the operating system gives up.
+    pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeouts.connect = timeout.into();
+        self
+    }
+
+    /// How long to wait for a new connection to be ready for queries, including the TLS handshake,
+    /// the Bolt version negotiation and the authentication.
+    /// Fails with [`Error::HandshakeTimeout`] otherwise.
+    ///
+    /// Defaults to 30 seconds, `None` waits forever.
+    pub fn handshake_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeouts.handshake = timeout.into();
+        self
+    }
+
+    /// How long to wait for each response of the server,
+    /// which fails with [`Error::ReadTimeout`] otherwise.
+    ///
+    /// This must be longer than the longest running query,
+    /// as the server only responds once the query has produced its first records,
+    /// unless the server sends keep-alive messages in the meantime, which restart the timeout.
+    ///
+    /// Defaults to `None`, which waits forever for a server that stops responding,
+    /// unless the server asks for a shorter timeout with its `connection.recv_timeout_seconds` hint.
+    pub fn read_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeouts.read = timeout.into();
+        self
+    }
+
+    /// How long to wait for a connection when all connections of the pool are in use,
+    /// which fails with [`Error::AcquireTimeout`] otherwise.
+    ///
+    /// Defaults to 60 seconds, `None` waits forever.
+    pub fn acquire_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeouts.acquire = timeout.into();
+        self
+    }
+
+    /// The manager that keeps track of bookmarks for causal consistency.
+    ///
+    /// Pass the same manager to several [`crate::Graph`] instances to read
+    /// the writes of one from the other.
+    /// Defaults to a new [`DefaultBookmarkManager`].
+    pub fn with_bookmark_manager(mut self, bookmark_manager: Arc<dyn BookmarkManager>) -> Self {
+        self.bookmark_manager = Some(bookmark_manager);
+        self
+    }
+
+    /// Calls the handler with every notification that the server reports about a query,
+    /// e.g. to fail tests on deprecated syntax.
+    ///
+    /// Notifications are logged regardless, warnings with [`log::warn!`],
+    /// everything else with [`log::debug!`].
+    ///
+    /// ```
+    /// use neo4rs::{ConfigBuilder, NotificationCategory};
+    ///
+    /// let config = ConfigBuilder::default().with_notification_handler(|notification| {
+    ///     if notification.category() == NotificationCategory::Deprecation {
+    ///         panic!("deprecated: {notification}");
+    ///     }
+    /// });
+    /// ```
+    pub fn with_notification_handler(
+        mut self,
+        handler: impl Fn(&Notification) + Send + Sync + 'static,
+    ) -> Self {
+        self.notifications = NotificationHook::new(handler);
+        self
+    }
+
+    /// Filters the notifications of all queries, which requires Bolt 5.2 or later.
+    /// Connections to older servers fail with [`Error::NotificationFilterNotSupported`].
+    ///
+    /// Sessions and transactions can override the filter, see [`SessionConfig::notification_filter`]
+    /// and [`TxConfig::notification_filter`].
+    pub fn with_notification_filter(mut self, filter: NotificationFilter) -> Self {
+        self.notification_filter = filter;
+        self
+    }
+
+    /// Records the text of every query as `db.query.text` in the spans of the `tracing` feature.
+    ///
+    /// Defaults to `false`, since queries might contain sensitive literals.
+    /// Parameters are never recorded.
+    #[cfg(feature = "tracing")]
+    pub fn trace_query_text(mut self, trace_query_text: bool) -> Self {
+        self.trace_query_text = trace_query_text;
         self
     }
 
     pub fn build(self) -> Result<Config> {
-        if let (Some(uri), Some(user), Some(password)) = (self.uri, self.user, self.password) {
+        let auth = match (self.auth, self.user, self.password) {
+            (Some(auth), _, _) => Some(auth),
+            (None, Some(user), Some(password)) => Some(AuthToken::basic(user, password)),
+            _ => None,
+        };
+        let auth = self.auth_manager.or_else(|| {
+            auth.map(|auth| Arc::new(StaticAuthManager::new(auth)) as Arc<dyn AuthManager>)
+        });
+        if let (Some(uri), Some(auth)) = (self.uri, auth) {
             Ok(Config {
                 uri,
-                user,
-                password,
+                auth,
                 fetch_size: self.fetch_size,
-                max_connections: self.max_connections,
+                pool: self.pool,
                 db: self.db,
-                client_certificate: self.client_certificate,
+                tls: self.tls,
+                timeouts: self.timeouts,
+                bookmark_manager: self.bookmark_manager,
+                notifications: self.notifications,
+                notification_filter: self.notification_filter,
+                #[cfg(feature = "tracing")]
+                trace_query_text: self.trace_query_text,
             })
         } else {
             Err(Error::InvalidConfig)
@@ -160,10 +653,18 @@ impl Default for ConfigBuilder {
             uri: None,
             user: None,
             password: None,
-            db: DEFAULT_DATABASE.into(),
-            max_connections: DEFAULT_MAX_CONNECTIONS,
+            auth: None,
+            auth_manager: None,
+            db: Some(DEFAULT_DATABASE.into()),
+            pool: PoolOptions::default(),
             fetch_size: DEFAULT_FETCH_SIZE,
-            client_certificate: None,
+            tls: TlsConfig::default(),
+            timeouts: Timeouts::default(),
+            bookmark_manager: None,
+            notifications: NotificationHook::default(),
+            notification_filter: NotificationFilter::default(),
+            #[cfg(feature = "tracing")]
+            trace_query_text: false,
         }
     }
 }
@@ -172,8 +673,8 @@ impl Default for ConfigBuilder {
 mod tests {
     use super::*;
 
-    #[test]
-    fn should_build_config() {
+    #[tokio::test]
+    async fn should_build_config() {
         let config = ConfigBuilder::default()
             .uri("127.0.0.1:7687")
             .user("some_user")
@@ -181,19 +682,50 @@ mod tests {
             .db("some_db")
             .fetch_size(10)
             .max_connections(5)
+            .min_connections(2)
+            .max_connection_lifetime(None)
+            .idle_time_before_liveness_check(Duration::from_secs(30))
+            .idle_timeout(Duration::from_secs(600))
+            .warm_up(true)
+            .connect_timeout(Duration::from_secs(5))
+            .handshake_timeout(Duration::from_secs(10))
+            .read_timeout(Duration::from_secs(15))
+            .acquire_timeout(None)
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
-        assert_eq!(&*config.db, "some_db");
+        assert_eq!(
+            config.auth.get_auth().await.unwrap(),
+            AuthToken::basic("some_user", "some_password")
+        );
+        assert_eq!(config.db.as_deref(), Some("some_db"));
         assert_eq!(config.fetch_size, 10);
-        assert_eq!(config.max_connections, 5);
-        assert!(config.client_certificate.is_none());
+        assert_eq!(
+            config.pool,
+            PoolOptions {
+                max_connections: 5,
+                min_connections: 2,
+                max_lifetime: None,
+                liveness_check: Some(Duration::from_secs(30)),
+                idle_timeout: Some(Duration::from_secs(600)),
+                warm_up: true,
+            }
+        );
+        assert_eq!(
+            config.timeouts,
+            Timeouts {
+                connect: Some(Duration::from_secs(5)),
+                handshake: Some(Duration::from_secs(10)),
+                read: Some(Duration::from_secs(15)),
+                acquire: None,
+            }
+        );
+        assert!(config.tls.ca_certificates().is_empty());
+        assert!(config.bookmark_manager.is_none());
     }
 
-    #[test]
-    fn should_build_with_defaults() {
+    #[tokio::test]
+    async fn should_build_with_defaults() {
         let config = ConfigBuilder::default()
             .uri("127.0.0.1:7687")
             .user("some_user")
@@ -201,12 +733,115 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
-        assert_eq!(&*config.db, "neo4j");
+        assert_eq!(
+            config.auth.get_auth().await.unwrap(),
+            AuthToken::basic("some_user", "some_password")
+        );
+        assert_eq!(config.db.as_deref(), Some("neo4j"));
         assert_eq!(config.fetch_size, 200);
-        assert_eq!(config.max_connections, 16);
-        assert!(config.client_certificate.is_none());
+        assert_eq!(config.pool.max_connections, 16);
+        assert_eq!(config.pool.min_connections, 0);
+        assert_eq!(config.pool.max_lifetime, Some(Duration::from_secs(3600)));
+        assert_eq!(config.pool.liveness_check, Some(Duration::ZERO));
+        assert_eq!(config.pool.idle_timeout, None);
+        assert!(!config.pool.warm_up);
+        assert_eq!(config.timeouts.connect, Some(Duration::from_secs(30)));
+        assert_eq!(config.timeouts.handshake, Some(Duration::from_secs(30)));
+        assert_eq!(config.timeouts.read, None);
+        assert_eq!(config.timeouts.acquire, Some(Duration::from_secs(60)));
+        assert!(config.tls.ca_certificates().is_empty());
+    }
+
+    #[test]
+    fn should_build_tx_config() {
+        let config = TxConfig::new()
+            .timeout(Duration::from_secs(2))
+            .metadata("app", "test")
+            .access_mode(AccessMode::Read);
+        assert_eq!(config.timeout_millis(), Some(2000));
+        assert_eq!(config.metadata.get::<String>("app").unwrap(), "test");
+        assert_eq!(config.mode(), AccessMode::Read);
+
+        let config = TxConfig::default();
+        assert_eq!(config.timeout_millis(), None);
+        assert_eq!(config.access_mode, None);
+        assert_eq!(config.mode(), AccessMode::Write);
+
+        let config = TxConfig::new().timeout(Duration::from_micros(10));
+        assert_eq!(config.timeout_millis(), Some(1));
+    }
+
+    #[test]
+    fn should_require_bolt_4_4_for_impersonation() {
+        let config = TxConfig::new().impersonate("jane");
+        assert_eq!(config.imp_user.as_deref(), Some("jane"));
+        assert!(config.check_version(Version::V4_4).is_ok());
+        assert!(matches!(
+            config.check_version(Version::V4_3),
+            Err(Error::ImpersonationNotSupported(_))
+        ));
+
+        assert!(TxConfig::new().check_version(Version::V4).is_ok());
+    }
+
+    #[test]
+    fn should_require_bolt_5_2_for_notification_filters() {
+        let config = TxConfig::new().notification_filter(NotificationFilter::disabled());
+        assert!(config.check_version(Version::V5_2).is_ok());
+        assert!(matches!(
+            config.check_version(Version::V5_1),
+            Err(Error::NotificationFilterNotSupported(_))
+        ));
+
+        let config = TxConfig::new().notification_filter(NotificationFilter::new());
+        assert!(config.check_version(Version::V4_4).is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_build_config_with_auth_token() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .with_auth(AuthToken::bearer("token"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.auth.get_auth().await.unwrap(),
+            AuthToken::bearer("token")
+        );
+
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .with_auth(AuthToken::none())
+            .build()
+            .unwrap();
+        assert_eq!(config.auth.get_auth().await.unwrap(), AuthToken::none());
+
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .with_auth(AuthToken::none())
+            .with_auth_manager(Arc::new(StaticAuthManager::new(AuthToken::bearer(
+                "managed",
+            ))))
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.auth.get_auth().await.unwrap(),
+            AuthToken::bearer("managed")
+        );
+    }
+
+    #[test]
+    fn should_build_config_with_home_database() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .with_home_database()
+            .build()
+            .unwrap();
+        assert_eq!(config.db, None);
     }
 
     #[test]
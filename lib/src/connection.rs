@@ -1,17 +1,27 @@
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use crate::bolt::{ExpectedResponse, Message, MessageResponse};
 use crate::{
-    auth::ClientCertificate,
-    errors::{Error, Result},
+    auth::{AuthManager, ConnectionAuth, Credentials},
+    config::Timeouts,
+    errors::{
+        Error, Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind, Result,
+    },
     messages::{BoltRequest, BoltResponse, HelloBuilder},
+    notification::{NotificationFilter, NotificationHook},
+    result_summary::ResultSummary,
+    routing::BoltServer,
+    telemetry::{self, RequestSpans},
+    tls::TlsConfig,
     version::Version,
-    BoltMap,
+    BoltList, BoltMap, Success,
 };
 use bytes::{Bytes, BytesMut};
-use log::warn;
-use std::fs::File;
-use std::io::BufReader;
-use std::{mem, sync::Arc};
+use std::{
+    future::Future,
+    mem,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use stream::ConnectionStream;
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt, BufStream},
@@ -21,7 +31,7 @@ use tokio_rustls::client::TlsStream;
 use tokio_rustls::{
     rustls::{
         pki_types::{IpAddr, Ipv4Addr, Ipv6Addr, ServerName},
-        ClientConfig, RootCertStore,
+        ClientConfig,
     },
     TlsConnector,
 };
@@ -32,128 +42,217 @@ const MAX_CHUNK_SIZE: usize = 65_535 - mem::size_of::<u16>();
 #[derive(Debug)]
 pub struct Connection {
     version: Version,
+    server: BoltServer,
+    credentials: Credentials,
     stream: BufStream<ConnectionStream>,
+    read_timeout: Option<Duration>,
+    hints: ConnectionHints,
+    state: ServerState,
+    last_activity: Instant,
+    spans: RequestSpans,
+    notifications: NotificationHook,
+    /// Set once the connection is in an unknown state, e.g. after a response timed out,
+    /// so that it is discarded instead of being used again.
+    defunct: bool,
 }
 
 impl Connection {
     pub(crate) async fn new(info: &ConnectionInfo) -> Result<Connection> {
-        let mut hello_builder = HelloBuilder::new(&*info.user, &*info.password);
+        let span = telemetry::connect(&info.server());
+        let connection = telemetry::instrument(span.clone(), Self::open(info)).await;
+        match &connection {
+            Ok(connection) => telemetry::negotiated(&span, connection.version),
+            Err(_) => telemetry::finished(&span, &connection),
+        }
+        connection
+    }
+
+    async fn open(info: &ConnectionInfo) -> Result<Connection> {
+        let credentials = info.auth.current().await?;
+        let mut hello_builder = HelloBuilder::new(credentials.token.clone());
         if let Routing::Yes(routing) = &info.routing {
             hello_builder.with_routing(routing.clone());
         };
+        hello_builder.with_notification_filter(info.notification_filter.clone());
+
+        let stream = with_timeout(info.timeouts.connect, Error::ConnectTimeout, async {
+            Ok(match &info.host {
+                Host::Domain(domain) => TcpStream::connect((&**domain, info.port)).await?,
+                Host::Ipv4(ip) => TcpStream::connect((*ip, info.port)).await?,
+                Host::Ipv6(ip) => TcpStream::connect((*ip, info.port)).await?,
+            })
+        })
+        .await?;
+
+        let mut connection =
+            with_timeout(info.timeouts.handshake, Error::HandshakeTimeout, async {
+                let (stream, version) = Self::handshake(info, stream).await?;
+                let server = info.server();
+                let spans = RequestSpans::new(server.clone(), info.trace_query_text);
+                Self::init(hello_builder, credentials, stream, version, server, spans).await
+            })
+            .await?;
+        // the responses during the handshake are covered by the handshake timeout
+        connection.read_timeout = info.timeouts.read;
+        connection.notifications = info.notifications.clone();
+        Ok(connection)
+    }
 
-        let stream = match &info.host {
-            Host::Domain(domain) => TcpStream::connect((&**domain, info.port)).await?,
-            Host::Ipv4(ip) => TcpStream::connect((*ip, info.port)).await?,
-            Host::Ipv6(ip) => TcpStream::connect((*ip, info.port)).await?,
-        };
-
-        match info.encryption {
-            Encryption::No => Self::new_unencrypted(stream, hello_builder).await,
-            Encryption::Tls => {
-                if let Some(certificate) = info.client_certificate.as_ref() {
-                    Self::new_tls_with_certificate(stream, &info.host, hello_builder, certificate)
-                        .await
-                } else {
-                    Self::new_tls(stream, &info.host, hello_builder).await
+    /// Secures the stream if required and negotiates the Bolt version.
+    async fn handshake(
+        info: &ConnectionInfo,
+        stream: TcpStream,
+    ) -> Result<(BufStream<ConnectionStream>, Version)> {
+        let span = telemetry::handshake(&info.server());
+        let result = telemetry::instrument(span.clone(), async {
+            let stream: ConnectionStream = match &info.encryption {
+                Encryption::No => stream.into(),
+                Encryption::Tls(config) | Encryption::SelfSignedTls(config) => {
+                    Self::build_stream(stream, &info.host, info.server_name.clone(), config.clone())
+                        .await?
+                        .into()
                 }
-            }
+            };
+            let mut stream = BufStream::new(stream);
+            stream.write_all(&[0x60, 0x60, 0xB0, 0x17]).await?;
+            stream.write_all(&Version::supported_versions()).await?;
+            stream.flush().await?;
+            let mut response = [0, 0, 0, 0];
+            stream.read_exact(&mut response).await?;
+            let version = Version::parse(response)?;
+            Ok((stream, version))
+        })
+        .await;
+        match &result {
+            Ok((_, version)) => telemetry::negotiated(&span, *version),
+            Err(_) => telemetry::finished(&span, &result),
         }
+        result
     }
 
-    async fn new_unencrypted(stream: TcpStream, hello_builder: HelloBuilder) -> Result<Connection> {
-        Self::init(hello_builder, stream).await
-    }
-
-    async fn new_tls<T: AsRef<str>>(
+    async fn build_stream<T: AsRef<str>>(
         stream: TcpStream,
         host: &Host<T>,
-        hello_builder: HelloBuilder,
-    ) -> Result<Connection> {
-        let root_cert_store = Self::build_cert_store();
-        let stream = Self::build_stream(stream, host, root_cert_store).await?;
+        server_name: Option<ServerName<'static>>,
+        config: Arc<ClientConfig>,
+    ) -> Result<TlsStream<TcpStream>, Error> {
+        let connector = TlsConnector::from(config);
 
-        Self::init(hello_builder, stream).await
+        let domain = match (server_name, host) {
+            (Some(server_name), _) => server_name,
+            (None, Host::Domain(domain)) => ServerName::try_from(domain.as_ref().to_owned())
+                .map_err(|_| Error::InvalidDnsName(domain.as_ref().to_owned()))?,
+            (None, Host::Ipv4(ip)) => ServerName::IpAddress(IpAddr::V4(Ipv4Addr::from(*ip))),
+            (None, Host::Ipv6(ip)) => ServerName::IpAddress(IpAddr::V6(Ipv6Addr::from(*ip))),
+        };
+
+        let stream = connector.connect(domain, stream).await?;
+        Ok(stream)
     }
 
-    async fn new_tls_with_certificate<T: AsRef<str>>(
-        stream: TcpStream,
-        host: &Host<T>,
+    async fn init(
         hello_builder: HelloBuilder,
-        certificate: &ClientCertificate,
+        credentials: Credentials,
+        stream: BufStream<ConnectionStream>,
+        version: Version,
+        server: BoltServer,
+        mut spans: RequestSpans,
     ) -> Result<Connection> {
-        let mut root_cert_store = Self::build_cert_store();
+        spans.negotiated(version);
+        let mut connection = Connection {
+            version,
+            server,
+            credentials,
+            stream,
+            read_timeout: None,
+            hints: ConnectionHints::default(),
+            state: ServerState::default(),
+            last_activity: Instant::now(),
+            spans,
+            notifications: NotificationHook::default(),
+            defunct: false,
+        };
+        let hello_builder = hello_builder.version(version);
+        hello_builder.check_version()?;
+        let logon = hello_builder.logon();
+        match connection.send_recv(hello_builder.build()).await? {
+            BoltResponse::Success(msg) => connection.hints = ConnectionHints::new(&msg),
+            BoltResponse::Failure(msg) => return Err(authentication_error(msg.into_error())),
+            msg => return Err(msg.into_error("HELLO")),
+        }
+        if let Some(logon) = logon {
+            connection.logon(logon).await?;
+        }
+        Ok(connection)
+    }
 
-        let cert_file = File::open(certificate.cert_file.as_os_str())?;
-        let mut reader = BufReader::new(cert_file);
-        let certs = rustls_pemfile::certs(&mut reader).flatten();
-        root_cert_store.add_parsable_certificates(certs);
+    pub(crate) fn version(&self) -> Version {
+        self.version
+    }
 
-        let stream = Self::build_stream(stream, host, root_cert_store).await?;
-        Self::init(hello_builder, stream).await
+    /// Reads the summary of a query from the success of the PULL or DISCARD that completed it,
+    /// together with the `t_first` of the success of RUN, and reports its notifications.
+    pub(crate) fn summary(&self, t_first: Option<i64>, success: &Success) -> ResultSummary {
+        let summary = ResultSummary::new(&self.server, t_first, success);
+        self.notifications.notify(&summary);
+        summary
     }
 
-    fn build_cert_store() -> RootCertStore {
-        let mut root_cert_store = RootCertStore::empty();
-        match rustls_native_certs::load_native_certs() {
-            Ok(certs) => {
-                root_cert_store.add_parsable_certificates(certs);
-            }
-            Err(e) => {
-                warn!("Failed to load native certificates: {e}");
-            }
-        }
-        root_cert_store
+    /// Returns `true` if the server is ready for new work without a RESET,
+    /// i.e. there is no open transaction, no open result and no failure.
+    pub(crate) fn is_ready(&self) -> bool {
+        self.state.is_ready()
     }
 
-    async fn build_stream<T: AsRef<str>>(
-        stream: TcpStream,
-        host: &Host<T>,
-        root_cert_store: RootCertStore,
-    ) -> Result<TlsStream<TcpStream>, Error> {
-        let config = ClientConfig::builder()
-            .with_root_certificates(root_cert_store)
-            .with_no_client_auth();
+    /// How long the connection has not sent or received anything.
+    pub(crate) fn idle_time(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
 
-        let config = Arc::new(config);
-        let connector = TlsConnector::from(config);
+    /// Returns `true` if the connection must not be used anymore.
+    pub(crate) fn is_defunct(&self) -> bool {
+        self.defunct
+    }
 
-        let domain = match host {
-            Host::Domain(domain) => ServerName::try_from(domain.as_ref().to_owned())
-                .map_err(|_| Error::InvalidDnsName(domain.as_ref().to_owned()))?,
-            Host::Ipv4(ip) => ServerName::IpAddress(IpAddr::V4(Ipv4Addr::from(*ip))),
-            Host::Ipv6(ip) => ServerName::IpAddress(IpAddr::V6(Ipv6Addr::from(*ip))),
-        };
+    /// Returns `true` if the connection can switch to other credentials with LOGOFF and LOGON,
+    /// which requires Bolt 5.1 or later.
+    pub(crate) fn can_reauthenticate(&self) -> bool {
+        self.version >= Version::V5_1
+    }
 
-        let stream = connector.connect(domain, stream).await?;
-        Ok(stream)
+    pub(crate) fn is_authenticated_with(&self, credentials: &Credentials) -> bool {
+        self.credentials == *credentials
     }
 
-    async fn init(
-        hello_builder: HelloBuilder,
-        stream: impl Into<ConnectionStream>,
-    ) -> Result<Connection> {
-        let mut stream = BufStream::new(stream.into());
-        stream.write_all(&[0x60, 0x60, 0xB0, 0x17]).await?;
-        stream.write_all(&Version::supported_versions()).await?;
-        stream.flush().await?;
-        let mut response = [0, 0, 0, 0];
-        stream.read_exact(&mut response).await?;
-        let version = Version::parse(response)?;
-        let mut connection = Connection { version, stream };
-        let hello = hello_builder.version(version).build();
-        match connection.send_recv(hello).await? {
-            BoltResponse::Success(_msg) => Ok(connection),
-            BoltResponse::Failure(msg) => {
-                Err(Error::AuthenticationError(msg.get("message").unwrap()))
-            }
-            msg => Err(msg.into_error("HELLO")),
+    /// Authenticates the connection again with the given credentials, without reconnecting.
+    ///
+    /// The credentials are updated up front, so that a connection that failed to switch
+    /// never passes as being authenticated with the credentials of the pool.
+    pub(crate) async fn reauthenticate(&mut self, credentials: Credentials) -> Result<()> {
+        let logon = BoltRequest::logon(&credentials.token);
+        self.credentials = credentials;
+        match self.send_recv(BoltRequest::logoff()).await? {
+            BoltResponse::Success(_) => {}
+            BoltResponse::Failure(f) => return Err(Error::Neo4j(f.into_error())),
+            msg => return Err(msg.into_error("LOGOFF")),
+        }
+        self.logon(logon).await
+    }
+
+    async fn logon(&mut self, logon: BoltRequest) -> Result<()> {
+        match self.send_recv(logon).await? {
+            BoltResponse::Success(_msg) => Ok(()),
+            BoltResponse::Failure(msg) => Err(authentication_error(msg.into_error())),
+            msg => Err(msg.into_error("LOGON")),
         }
     }
 
     pub async fn reset(&mut self) -> Result<()> {
         match self.send_recv(BoltRequest::reset()).await? {
-            BoltResponse::Success(_) => Ok(()),
+            BoltResponse::Success(_) => {
+                self.state = ServerState::default();
+                Ok(())
+            }
             BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
             msg => Err(msg.into_error("RESET")),
         }
@@ -175,6 +274,8 @@ impl Connection {
     }
 
     pub async fn send(&mut self, message: BoltRequest) -> Result<()> {
+        self.state.sent(&message);
+        self.spans.sent(&message);
         let bytes: Bytes = message.into_bytes(self.version)?;
         self.send_bytes(bytes).await
     }
@@ -182,13 +283,20 @@ impl Connection {
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     #[allow(unused)]
     pub(crate) async fn send_as<T: Message>(&mut self, message: T) -> Result<()> {
+        // the state of the server is not tracked for these messages
+        self.state.needs_reset = true;
         let bytes = message.to_bytes()?;
         self.send_bytes(bytes).await
     }
 
     pub async fn recv(&mut self) -> Result<BoltResponse> {
-        let bytes = self.recv_bytes().await?;
-        BoltResponse::parse(self.version, bytes)
+        let response = match self.recv_bytes().await {
+            Ok(bytes) => BoltResponse::parse(self.version, bytes),
+            Err(e) => Err(e),
+        };
+        self.state.received(&response);
+        self.spans.received(&response);
+        response
     }
 
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
@@ -207,23 +315,46 @@ impl Connection {
         }
         self.stream.write_all(&end_marker).await?;
         self.stream.flush().await?;
+        self.last_activity = Instant::now();
         Ok(())
     }
 
     async fn recv_bytes(&mut self) -> Result<Bytes> {
+        // the shorter of the configured timeout and the one the server asked for applies
+        let timeout = match (self.read_timeout, self.hints.recv_timeout) {
+            (Some(configured), Some(hint)) => Some(configured.min(hint)),
+            (configured, hint) => configured.or(hint),
+        };
+        let result = self.read_message(timeout).await;
+        if let Err(Error::ReadTimeout) = result {
+            // the response might still arrive and would then be mistaken for the next one
+            self.defunct = true;
+        }
+        result
+    }
+
+    /// The timeout applies to every chunk, so that the NOOP chunks that the server sends
+    /// to keep the connection alive during a long running query restart it.
+    async fn read_message(&mut self, timeout: Option<Duration>) -> Result<Bytes> {
         let mut bytes = BytesMut::new();
         let mut chunk_size = 0;
         while chunk_size == 0 {
-            chunk_size = self.read_chunk_size().await?;
+            chunk_size = with_timeout(timeout, Error::ReadTimeout, self.read_chunk_size()).await?;
         }
 
         while chunk_size > 0 {
-            self.read_chunk(chunk_size, &mut bytes).await?;
-            chunk_size = self.read_chunk_size().await?;
+            with_timeout(
+                timeout,
+                Error::ReadTimeout,
+                self.read_chunk(chunk_size, &mut bytes),
+            )
+            .await?;
+            chunk_size = with_timeout(timeout, Error::ReadTimeout, self.read_chunk_size()).await?;
         }
 
         let bytes = bytes.freeze();
         Self::dbg("recv", &bytes);
+        self.last_activity = Instant::now();
         Ok(bytes)
     }
 
@@ -255,24 +386,124 @@ impl Connection {
     }
 }
 
-#[derive(Debug)]
+/// An expired token is reported as a [`Error::Neo4j`] error,
+/// so that the auth manager gets a chance to refresh it before the work is retried.
+fn authentication_error(error: Neo4jError) -> Error {
+    match error.kind() {
+        Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(
+            Neo4jSecurityErrorKind::TokenExpired,
+        )) => Error::Neo4j(error),
+        _ => Error::AuthenticationError(error.message().to_owned()),
+    }
+}
+
+/// Follows the requests and responses to tell whether the server is ready for new work.
+#[derive(Debug, Default)]
+struct ServerState {
+    /// The number of requests that the server has not responded to yet.
+    pending: usize,
+    in_txn: bool,
+    open_result: bool,
+    /// The server has failed or is in an unknown state.
+    needs_reset: bool,
+}
+
+impl ServerState {
+    fn sent(&mut self, request: &BoltRequest) {
+        self.pending += 1;
+        match request {
+            BoltRequest::Begin(_) => self.in_txn = true,
+            BoltRequest::Commit(_) | BoltRequest::Rollback(_) => self.in_txn = false,
+            _ => {}
+        }
+    }
+
+    fn received(&mut self, response: &Result<BoltResponse>) {
+        match response {
+            Ok(BoltResponse::Record(_)) => {}
+            Ok(BoltResponse::Success(success)) => {
+                self.pending = self.pending.saturating_sub(1);
+                // RUN opens a result with its fields, PULL and DISCARD keep it open with has_more
+                self.open_result = success.get::<BoltList>("fields").is_ok()
+                    || success.get::<bool>("has_more").unwrap_or(false);
+            }
+            Ok(BoltResponse::Failure(_)) | Err(_) => {
+                self.pending = self.pending.saturating_sub(1);
+                self.needs_reset = true;
+            }
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.pending == 0 && !self.in_txn && !self.open_result && !self.needs_reset
+    }
+}
+
+/// The hints the server sends along with the success of HELLO.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ConnectionHints {
+    /// The server closes connections that do not receive anything for this long,
+    /// the driver should give up on responses after the same time.
+    recv_timeout: Option<Duration>,
+}
+
+impl ConnectionHints {
+    fn new(success: &Success) -> Self {
+        let Ok(hints) = success.get::<BoltMap>("hints") else {
+            return Self::default();
+        };
+        let recv_timeout = match hints.get::<i64>("connection.recv_timeout_seconds") {
+            Ok(seconds) if seconds > 0 => Some(Duration::from_secs(seconds.unsigned_abs())),
+            Ok(seconds) => {
+                log::warn!("Ignoring the invalid hint connection.recv_timeout_seconds={seconds}");
+                None
+            }
+            Err(_) => None,
+        };
+        Self { recv_timeout }
+    }
+}
+
+/// Awaits the future, or fails with the given error once the timeout has elapsed.
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    error: Error,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| error)?,
+        None => future.await,
+    }
+}
+
+#[derive(Debug, Clone)]
 pub(crate) struct ConnectionInfo {
-    user: Arc<str>,
-    password: Arc<str>,
+    auth: ConnectionAuth,
+    timeouts: Timeouts,
     host: Host<Arc<str>>,
     port: u16,
     encryption: Encryption,
+    /// Overrides the host as the name the certificate of the server is verified against.
+    server_name: Option<ServerName<'static>>,
     routing: Routing,
-    client_certificate: Option<ClientCertificate>,
+    /// Records the text of the queries in the spans of the `tracing` feature.
+    trace_query_text: bool,
+    notifications: NotificationHook,
+    notification_filter: NotificationFilter,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Encryption {
     No,
-    Tls,
+    /// The `+s` schemes, which require a certificate signed by a trusted CA.
+    Tls(Arc<ClientConfig>),
+    /// The `+ssc` schemes, which also accept a self-signed certificate.
+    SelfSignedTls(Arc<ClientConfig>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum Routing {
     No,
     Yes(BoltMap),
@@ -281,11 +512,11 @@ pub(crate) enum Routing {
 impl ConnectionInfo {
     pub(crate) fn new(
         uri: &str,
-        user: &str,
-        password: &str,
-        client_certificate: Option<&ClientCertificate>,
+        auth: Arc<dyn AuthManager>,
+        tls: &TlsConfig,
+        timeouts: Timeouts,
     ) -> Result<Self> {
-        let mut url = NeoUrl::parse(uri)?;
+        let url = NeoUrl::parse(uri)?;
 
         let host = url.host();
         let host = match host {
@@ -298,36 +529,92 @@ impl ConnectionInfo {
 
         let (routing, encryption) = match url.scheme() {
             "bolt" | "" => (false, Encryption::No),
-            "bolt+s" => (false, Encryption::Tls),
-            "bolt+ssc" => (false, Encryption::Tls),
+            "bolt+s" => (false, Encryption::Tls(tls.client_config()?)),
+            "bolt+ssc" => (
+                false,
+                Encryption::SelfSignedTls(tls.self_signed_client_config()?),
+            ),
             "neo4j" => (true, Encryption::No),
-            "neo4j+s" => (true, Encryption::Tls),
-            "neo4j+ssc" => (true, Encryption::Tls),
+            "neo4j+s" => (true, Encryption::Tls(tls.client_config()?)),
+            "neo4j+ssc" => (
+                true,
+                Encryption::SelfSignedTls(tls.self_signed_client_config()?),
+            ),
             otherwise => return Err(Error::UnsupportedScheme(otherwise.to_owned())),
         };
 
+        url.warn_on_unexpected_components(routing);
+
         let routing = if routing {
-            log::warn!(concat!(
-                "This driver does not yet implement client-side routing. ",
-                "It is possible that operations against a cluster (such as Aura) will fail."
-            ));
-            Routing::Yes(url.routing_context())
+            Routing::Yes(url.routing_context()?)
         } else {
             Routing::No
         };
 
-        url.warn_on_unexpected_components();
-
         Ok(Self {
-            user: user.into(),
-            password: password.into(),
+            auth: ConnectionAuth::new(auth),
+            timeouts,
             host,
             port,
             encryption,
+            server_name: tls.server_name()?,
             routing,
-            client_certificate: client_certificate.cloned(),
+            trace_query_text: false,
+            notifications: NotificationHook::default(),
+            notification_filter: NotificationFilter::default(),
         })
     }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn trace_query_text(self, trace_query_text: bool) -> Self {
+        Self {
+            trace_query_text,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_notifications(self, notifications: NotificationHook) -> Self {
+        Self {
+            notifications,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_notification_filter(self, notification_filter: NotificationFilter) -> Self {
+        Self {
+            notification_filter,
+            ..self
+        }
+    }
+
+    pub(crate) fn auth(&self) -> &ConnectionAuth {
+        &self.auth
+    }
+
+    pub(crate) fn timeouts(&self) -> Timeouts {
+        self.timeouts
+    }
+
+    /// The address of the server this info connects to.
+    pub(crate) fn server(&self) -> BoltServer {
+        BoltServer::new(self.host.clone(), self.port)
+    }
+
+    /// Returns a copy of this info that connects to another server of the same cluster.
+    pub(crate) fn for_server(&self, server: &BoltServer) -> Self {
+        Self {
+            host: server.host.clone(),
+            port: server.port,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn routing_context(&self) -> Option<&BoltMap> {
+        match &self.routing {
+            Routing::Yes(context) => Some(context),
+            Routing::No => None,
+        }
+    }
 }
 
 struct NeoUrl(Url);
@@ -358,11 +645,38 @@ impl NeoUrl {
         self.0.port().unwrap_or(7687)
     }
 
-    fn routing_context(&mut self) -> BoltMap {
-        BoltMap::new()
+    /// The routing context is sent to the server in HELLO and ROUTE messages
+    /// and can be used by server-side routing policies.
+    /// It consists of the query parameters of the URI and the initial address of the server.
+    fn routing_context(&self) -> Result<BoltMap> {
+        let mut context = BoltMap::new();
+        for (key, value) in self.0.query_pairs() {
+            if key.is_empty() || value.is_empty() {
+                return Err(Error::InvalidRoutingContext(format!(
+                    "empty key or value in `{key}={value}`"
+                )));
+            }
+            if key == "address" {
+                return Err(Error::InvalidRoutingContext(
+                    "the key `address` is reserved for the server address".into(),
+                ));
+            }
+            if context.value.contains_key(&*key) {
+                return Err(Error::InvalidRoutingContext(format!(
+                    "duplicate key `{key}`"
+                )));
+            }
+            context.put(key.as_ref().into(), value.as_ref().into());
+        }
+
+        let host = self.0.host_str().unwrap_or_default();
+        let address = format!("{}:{}", host, self.port());
+        context.put("address".into(), address.into());
+
+        Ok(context)
     }
 
-    fn warn_on_unexpected_components(&self) {
+    fn warn_on_unexpected_components(&self, routing: bool) {
         if !self.0.username().is_empty() || self.0.password().is_some() {
             log::warn!(concat!(
                 "URI contained auth credentials, which are ignored.",
@@ -373,10 +687,10 @@ impl NeoUrl {
             log::warn!("URI contained a path, which is ignored.");
         }
 
-        if self.0.query().is_some() {
+        if !routing && self.0.query().is_some() {
             log::warn!(concat!(
-                "This client does not yet support client-side routing.",
-                "The routing context passed as a query to the URI is ignored."
+                "URI contained a query, which is ignored. ",
+                "A routing context can only be passed with a neo4j:// scheme."
             ));
         }
 
@@ -483,10 +797,230 @@ mod stream {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
     use url::Host;
 
-    use super::NeoUrl;
+    use super::{AuthManager, Connection, ConnectionHints, ConnectionInfo, NeoUrl, Routing};
+    use crate::{
+        auth::StaticAuthManager, config::Timeouts, tls::TlsConfig, AuthToken, BoltMap, BoltType,
+        Error, Success, Version,
+    };
+
+    pub(crate) fn no_auth() -> Arc<dyn AuthManager> {
+        Arc::new(StaticAuthManager::new(AuthToken::none()))
+    }
+
+    /// Starts a server that runs the given script on the first connection
+    /// and then keeps the connection open without responding.
+    async fn unresponsive_server<F, Fut>(script: F) -> ConnectionInfo
+    where
+        F: FnOnce(TcpStream) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = TcpStream> + Send,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _stream = script(stream).await;
+            std::future::pending::<()>().await;
+        });
+
+        let timeouts = Timeouts {
+            handshake: Some(Duration::from_millis(100)),
+            read: Some(Duration::from_millis(100)),
+            ..Timeouts::default()
+        };
+        ConnectionInfo::new(
+            &format!("bolt://{address}"),
+            no_auth(),
+            &TlsConfig::default(),
+            timeouts,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_time_out_during_the_handshake() {
+        let info = unresponsive_server(|stream| async { stream }).await;
+        let error = Connection::new(&info).await.unwrap_err();
+        assert!(matches!(error, Error::HandshakeTimeout), "{error:?}");
+    }
+
+    /// Answers the handshake and HELLO with Bolt 4.4 and the given SUCCESS metadata.
+    pub(crate) async fn hello(stream: TcpStream, metadata: &[u8]) -> TcpStream {
+        hello_with_version(stream, [0, 0, 4, 4], metadata).await
+    }
+
+    /// Answers the handshake with the given version, e.g. `[0, 0, 1, 5]` for Bolt 5.1,
+    /// and the HELLO with the given SUCCESS metadata.
+    pub(crate) async fn hello_with_version(
+        mut stream: TcpStream,
+        version: [u8; 4],
+        metadata: &[u8],
+    ) -> TcpStream {
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).await.unwrap();
+        stream.write_all(&version).await.unwrap();
+        loop {
+            let chunk_size = stream.read_u16().await.unwrap();
+            if chunk_size == 0 {
+                break;
+            }
+            let mut chunk = vec![0; usize::from(chunk_size)];
+            stream.read_exact(&mut chunk).await.unwrap();
+        }
+
+        let mut success = vec![0xB1, 0x70];
+        success.extend_from_slice(metadata);
+        stream
+            .write_u16(u16::try_from(success.len()).unwrap())
+            .await
+            .unwrap();
+        stream.write_all(&success).await.unwrap();
+        stream.write_all(&[0, 0]).await.unwrap();
+        stream
+    }
+
+    #[tokio::test]
+    async fn should_time_out_waiting_for_a_response() {
+        let info = unresponsive_server(|stream| hello(stream, &[0xA0])).await;
+
+        let mut connection = Connection::new(&info).await.unwrap();
+        assert!(!connection.is_defunct());
+        let error = connection.reset().await.unwrap_err();
+        assert!(matches!(error, Error::ReadTimeout), "{error:?}");
+        assert!(connection.is_defunct());
+    }
+
+    #[tokio::test]
+    async fn should_restart_the_read_timeout_on_keep_alive_chunks() {
+        let info = unresponsive_server(|stream| async {
+            let mut stream = hello(stream, &[0xA0]).await;
+            let mut reset = [0; 6];
+            stream.read_exact(&mut reset).await.unwrap();
+            // NOOP chunks for three times the read timeout, then the SUCCESS
+            for _ in 0..6 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                stream.write_all(&[0, 0]).await.unwrap();
+            }
+            stream
+                .write_all(&[0, 3, 0xB1, 0x70, 0xA0, 0, 0])
+                .await
+                .unwrap();
+            stream
+        })
+        .await;
+
+        let mut connection = Connection::new(&info).await.unwrap();
+        connection.reset().await.unwrap();
+        assert!(!connection.is_defunct());
+    }
+
+    #[tokio::test]
+    async fn should_honour_the_recv_timeout_hint() {
+        let info = unresponsive_server(|stream| async {
+            let mut metadata = vec![0xA1, 0x85];
+            metadata.extend_from_slice(b"hints");
+            metadata.extend_from_slice(&[0xA1, 0xD0, 0x1F]);
+            metadata.extend_from_slice(b"connection.recv_timeout_seconds");
+            metadata.push(0x01);
+            hello(stream, &metadata).await
+        })
+        .await;
+        let info = ConnectionInfo {
+            timeouts: Timeouts::default(),
+            ..info
+        };
+
+        let mut connection = Connection::new(&info).await.unwrap();
+        assert_eq!(connection.hints.recv_timeout, Some(Duration::from_secs(1)));
+        let error = connection.reset().await.unwrap_err();
+        assert!(matches!(error, Error::ReadTimeout), "{error:?}");
+        assert!(connection.is_defunct());
+    }
+
+    #[test]
+    fn should_track_whether_the_server_is_ready() {
+        use super::{BoltRequest, BoltResponse, ServerState};
+        use crate::BoltList;
+
+        let success = |metadata: Vec<(&str, BoltType)>| {
+            let metadata = BoltMap::from_entries(metadata);
+            Ok(BoltResponse::Success(Success::new(metadata)))
+        };
+        let mut state = ServerState::default();
+        assert!(state.is_ready());
+
+        // an auto-commit query that is pulled in two batches
+        state.sent(&BoltRequest::run(None, "RETURN 1", BoltMap::default()));
+        assert!(!state.is_ready());
+        state.received(&success(vec![("fields", BoltType::List(BoltList::new()))]));
+        assert!(!state.is_ready());
+        state.sent(&BoltRequest::pull(1, -1));
+        state.received(&success(vec![("has_more", true.into())]));
+        assert!(!state.is_ready());
+        state.sent(&BoltRequest::pull(1, -1));
+        state.received(&success(vec![("bookmark", "bm".into())]));
+        assert!(state.is_ready());
+
+        // an auto-commit query whose records are discarded
+        state.sent(&BoltRequest::run(None, "RETURN 1", BoltMap::default()));
+        state.received(&success(vec![("fields", BoltType::List(BoltList::new()))]));
+        assert!(!state.is_ready());
+        state.sent(&BoltRequest::discard(-1));
+        state.received(&success(vec![("bookmark", "bm".into())]));
+        assert!(state.is_ready());
+
+        // a transaction that is left open
+        state.sent(&BoltRequest::begin(
+            None,
+            &[],
+            &Default::default(),
+            Version::V5_0,
+        ));
+        state.received(&success(vec![]));
+        assert!(!state.is_ready());
+        state.sent(&BoltRequest::commit());
+        state.received(&success(vec![("bookmark", "bm".into())]));
+        assert!(state.is_ready());
+
+        state.sent(&BoltRequest::reset());
+        state.received(&Err(Error::ReadTimeout));
+        assert!(!state.is_ready());
+    }
+
+    #[test]
+    fn should_ignore_invalid_hints() {
+        let hints = |hints: BoltMap| {
+            let metadata = BoltMap::from_entries([("hints", BoltType::Map(hints))]);
+            ConnectionHints::new(&Success::new(metadata))
+        };
+        let timeout = |value: BoltType| {
+            hints(BoltMap::from_entries([(
+                "connection.recv_timeout_seconds",
+                value,
+            )]))
+        };
+
+        assert_eq!(
+            ConnectionHints::new(&Success::new(BoltMap::new())).recv_timeout,
+            None
+        );
+        assert_eq!(hints(BoltMap::new()).recv_timeout, None);
+        assert_eq!(
+            timeout(120.into()).recv_timeout,
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(timeout(0.into()).recv_timeout, None);
+        assert_eq!(timeout((-1).into()).recv_timeout, None);
+        assert_eq!(timeout("120".into()).recv_timeout, None);
+    }
 
     #[test]
     fn should_parse_uri() {
@@ -511,4 +1045,68 @@ mod tests {
         assert_eq!(url.host(), Host::Domain("127.0.0.1"));
         assert_eq!(url.scheme(), "bolt");
     }
+
+    #[test]
+    fn should_parse_routing_context() {
+        let url = NeoUrl::parse("neo4j://localhost:4242?region=eu&policy=x").unwrap();
+        let context = url.routing_context().unwrap();
+        assert_eq!(context.len(), 3);
+        assert_eq!(context.get::<String>("region").unwrap(), "eu");
+        assert_eq!(context.get::<String>("policy").unwrap(), "x");
+        assert_eq!(context.get::<String>("address").unwrap(), "localhost:4242");
+    }
+
+    #[test]
+    fn should_add_default_port_to_routing_address() {
+        let url = NeoUrl::parse("neo4j://localhost").unwrap();
+        let context = url.routing_context().unwrap();
+        assert_eq!(context.len(), 1);
+        assert_eq!(context.get::<String>("address").unwrap(), "localhost:7687");
+    }
+
+    #[test]
+    fn should_reject_invalid_routing_context() {
+        for uri in [
+            "neo4j://localhost?address=other:7687",
+            "neo4j://localhost?region=eu&region=us",
+            "neo4j://localhost?region=",
+            "neo4j://localhost?=eu",
+        ] {
+            let url = NeoUrl::parse(uri).unwrap();
+            assert!(
+                matches!(url.routing_context(), Err(Error::InvalidRoutingContext(_))),
+                "{uri} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn should_only_route_with_neo4j_scheme() {
+        let info = ConnectionInfo::new(
+            "bolt://localhost?region=eu",
+            no_auth(),
+            &TlsConfig::default(),
+            Timeouts::default(),
+        )
+        .unwrap();
+        assert!(matches!(info.routing, Routing::No));
+
+        let info = ConnectionInfo::new(
+            "neo4j+s://localhost?region=eu",
+            no_auth(),
+            &TlsConfig::default(),
+            Timeouts::default(),
+        )
+        .unwrap();
+        let context = info.routing_context().cloned().unwrap_or_else(BoltMap::new);
+        assert_eq!(context.get::<String>("region").unwrap(), "eu");
+
+        assert!(ConnectionInfo::new(
+            "neo4j://localhost?address=x",
+            no_auth(),
+            &TlsConfig::default(),
+            Timeouts::default()
+        )
+        .is_err());
+    }
 }
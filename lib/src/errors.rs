@@ -1,6 +1,8 @@
+use std::fmt;
+
 #[cfg(feature = "unstable-serde-packstream-format")]
 use crate::packstream::{de, ser};
-use crate::DeError;
+use crate::{BoltMap, DeError};
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -30,9 +32,33 @@ pub enum Error {
     #[error("Invalid DNS name: {0}")]
     InvalidDnsName(String),
 
+    #[error("Invalid client identity: {0}")]
+    InvalidClientIdentity(String),
+
     #[error("connection error")]
     ConnectionError,
 
+    #[error("Timed out connecting to the server")]
+    ConnectTimeout,
+
+    #[error("Timed out during the handshake with the server")]
+    HandshakeTimeout,
+
+    #[error("Timed out waiting for a response from the server")]
+    ReadTimeout,
+
+    #[error("Timed out waiting for a connection from the pool")]
+    AcquireTimeout,
+
+    #[error("Invalid routing context in URI: {0}")]
+    InvalidRoutingContext(String),
+
+    #[error("Unable to retrieve routing information: {0}")]
+    RoutingTableError(String),
+
+    #[error("No server available to handle the request: {0}")]
+    ServiceUnavailable(String),
+
     #[error("attempted to serialize excessively long string")]
     StringTooLong,
 
@@ -63,7 +89,7 @@ pub enum Error {
     )]
     ProtocolMismatch(u32),
 
-    #[error("Neo4j error `{}`: {}", .0.code, .0.message)]
+    #[error(transparent)]
     Neo4j(Neo4jError),
 
     #[error("{0}")]
@@ -84,11 +110,36 @@ pub enum Error {
     #[error("{0}")]
     AuthenticationError(String),
 
+    #[error("Impersonation is not supported: {0}")]
+    ImpersonationNotSupported(String),
+
+    #[error("Session auth is not supported: {0}")]
+    SessionAuthNotSupported(String),
+
+    #[error("Notification filters are not supported: {0}")]
+    NotificationFilterNotSupported(String),
+
     #[error("{0}")]
     InvalidTypeMarker(String),
 
     #[error("{0}")]
-    DeserializationError(DeError),
+    DeserializationError(#[from] DeError),
+}
+
+impl Error {
+    /// Servers that cannot impersonate users, e.g. the community edition,
+    /// reject the `imp_user` with a generic failure, which is replaced by a dedicated error.
+    pub(crate) fn for_impersonation(self, imp_user: Option<&str>) -> Self {
+        match (self, imp_user) {
+            (Error::Neo4j(e), Some(user)) if e.is_impersonation_failure() => {
+                Error::ImpersonationNotSupported(format!(
+                    "cannot impersonate `{user}`: {}",
+                    e.message
+                ))
+            }
+            (e, _) => e,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -208,6 +259,7 @@ impl Neo4jErrorKind {
             kind: self,
             code,
             message,
+            gql: None,
         }
     }
 }
@@ -218,18 +270,112 @@ impl From<&str> for Neo4jErrorKind {
     }
 }
 
+/// How a GQL error is classified in its diagnostic record, see [`Neo4jError::classification`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GqlErrorClassification {
+    ClientError,
+    DatabaseError,
+    TransientError,
+    /// A classification that this version of the driver does not know, or none at all.
+    Unknown,
+}
+
+impl GqlErrorClassification {
+    fn parse(classification: Option<&str>) -> Self {
+        match classification {
+            Some("CLIENT_ERROR") => Self::ClientError,
+            Some("DATABASE_ERROR") => Self::DatabaseError,
+            Some("TRANSIENT_ERROR") => Self::TransientError,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A failure reported by the server.
+///
+/// Since Bolt 5.7, failures are also described as GQL errors, with a GQL status,
+/// a diagnostic record, and the errors that caused them,
+/// which are available as the [`std::error::Error::source`] chain.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Neo4jError {
     kind: Neo4jErrorKind,
     code: String,
     message: String,
+    /// Boxed, as errors are moved around a lot and most of them come from older servers.
+    gql: Option<Box<GqlError>>,
+}
+
+#[derive(Clone, Debug)]
+struct GqlError {
+    gql_status: Option<String>,
+    description: Option<String>,
+    diagnostic_record: Option<BoltMap>,
+    cause: Option<Neo4jError>,
 }
 
+/// The diagnostic record is left out, as it may contain floats, which are not `Eq`.
+impl PartialEq for GqlError {
+    fn eq(&self, other: &Self) -> bool {
+        self.gql_status == other.gql_status
+            && self.description == other.description
+            && self.cause == other.cause
+    }
+}
+
+impl Eq for GqlError {}
+
 impl Neo4jError {
     pub(crate) fn new(code: String, message: String) -> Self {
         Neo4jErrorKind::new(&code).new_error(code, message)
     }
 
+    /// Reads the metadata of a FAILURE, which is called `neo4j_code` instead of `code`
+    /// and has GQL fields and causes since Bolt 5.7.
+    pub(crate) fn from_failure(metadata: &BoltMap) -> Self {
+        let code = metadata
+            .get::<String>("neo4j_code")
+            .or_else(|_| metadata.get::<String>("code"))
+            .unwrap_or_default();
+        let message = metadata.get::<String>("message").unwrap_or_default();
+        Self::new(code, message).with_gql(
+            metadata.get::<String>("gql_status").ok(),
+            metadata.get::<String>("description").ok(),
+            metadata.get::<BoltMap>("diagnostic_record").ok(),
+            metadata
+                .get::<BoltMap>("cause")
+                .ok()
+                .map(|cause| Self::from_failure(&cause)),
+        )
+    }
+
+    /// Failures of servers before Bolt 5.7 have none of the GQL fields and stay without them.
+    pub(crate) fn with_gql(
+        self,
+        gql_status: Option<String>,
+        description: Option<String>,
+        diagnostic_record: Option<BoltMap>,
+        cause: Option<Neo4jError>,
+    ) -> Self {
+        if gql_status.is_none()
+            && description.is_none()
+            && diagnostic_record.is_none()
+            && cause.is_none()
+        {
+            return self;
+        }
+        let gql = GqlError {
+            gql_status,
+            description,
+            diagnostic_record,
+            cause,
+        };
+        Self {
+            gql: Some(Box::new(gql)),
+            ..self
+        }
+    }
+
     pub fn kind(&self) -> Neo4jErrorKind {
         self.kind
     }
@@ -242,8 +388,70 @@ impl Neo4jError {
         &self.message
     }
 
+    /// The GQL status code, e.g. `42001` for a syntax error (Bolt 5.7 and later).
+    pub fn gql_status(&self) -> Option<&str> {
+        self.gql.as_ref()?.gql_status.as_deref()
+    }
+
+    /// The description of the GQL status (Bolt 5.7 and later).
+    pub fn gql_status_description(&self) -> Option<&str> {
+        self.gql.as_ref()?.description.as_deref()
+    }
+
+    /// Details about the error, e.g. the `_classification` and the `_position` in the query
+    /// (Bolt 5.7 and later).
+    pub fn diagnostic_record(&self) -> Option<&BoltMap> {
+        self.gql.as_ref()?.diagnostic_record.as_ref()
+    }
+
+    /// The classification of the diagnostic record.
+    pub fn classification(&self) -> GqlErrorClassification {
+        GqlErrorClassification::parse(
+            self.diagnostic_record()
+                .and_then(|record| record.get::<&str>("_classification").ok()),
+        )
+    }
+
+    /// The error that caused this one, which is also its [`std::error::Error::source`].
+    pub fn cause(&self) -> Option<&Neo4jError> {
+        self.gql.as_ref()?.cause.as_ref()
+    }
+
+    /// The legacy code decides for client errors, as it tells some of them apart,
+    /// e.g. terminated transactions are transient errors that must not be retried.
+    /// For everything else, a transient GQL classification makes the error retryable too.
     pub(crate) fn can_retry(&self) -> bool {
-        self.kind.can_retry()
+        match self.kind {
+            Neo4jErrorKind::Client(_) => self.kind.can_retry(),
+            kind => {
+                kind.can_retry() || self.classification() == GqlErrorClassification::TransientError
+            }
+        }
+    }
+
+    /// Only the failure of servers that do not support impersonation at all,
+    /// a missing privilege to impersonate the user is a security error that is kept as it is.
+    fn is_impersonation_failure(&self) -> bool {
+        self.code == "Neo.ClientError.Statement.UnsupportedOperationError"
+            && self.message.to_ascii_lowercase().contains("impersonat")
+    }
+}
+
+impl fmt::Display for Neo4jError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // causes only have a GQL status
+        let code = match (&*self.code, self.gql_status()) {
+            ("", Some(gql_status)) => gql_status,
+            (code, _) => code,
+        };
+        write!(f, "Neo4j error `{code}`: {}", self.message)
+    }
+}
+
+impl std::error::Error for Neo4jError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause()
+            .map(|cause| cause as &(dyn std::error::Error + 'static))
     }
 }
 
@@ -251,7 +459,135 @@ impl std::convert::From<deadpool::managed::PoolError<Error>> for Error {
     fn from(e: deadpool::managed::PoolError<Error>) -> Self {
         match e {
             deadpool::managed::PoolError::Backend(e) => e,
+            deadpool::managed::PoolError::Timeout(_) => Error::AcquireTimeout,
             _ => Error::ConnectionError,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoltType;
+
+    #[test]
+    fn should_detect_impersonation_failures() {
+        let failure = || {
+            Error::Neo4j(Neo4jError::new(
+                "Neo.ClientError.Statement.UnsupportedOperationError".into(),
+                "Impersonation is not supported in community edition.".into(),
+            ))
+        };
+
+        assert!(matches!(
+            failure().for_impersonation(Some("jane")),
+            Error::ImpersonationNotSupported(msg) if msg.contains("`jane`")
+        ));
+        assert!(matches!(failure().for_impersonation(None), Error::Neo4j(_)));
+
+        let other = Error::Neo4j(Neo4jError::new(
+            "Neo.ClientError.Statement.SyntaxError".into(),
+            "Invalid input".into(),
+        ));
+        assert!(matches!(
+            other.for_impersonation(Some("jane")),
+            Error::Neo4j(_)
+        ));
+
+        let forbidden = Error::Neo4j(Neo4jError::new(
+            "Neo.ClientError.Security.Forbidden".into(),
+            "Cannot impersonate user 'jane' without the IMPERSONATE privilege.".into(),
+        ));
+        assert!(matches!(
+            forbidden.for_impersonation(Some("jane")),
+            Error::Neo4j(e) if e.code() == "Neo.ClientError.Security.Forbidden"
+        ));
+    }
+
+    fn diagnostic_record(classification: &str) -> BoltType {
+        BoltType::Map(BoltMap::from_entries(vec![(
+            "_classification",
+            classification.into(),
+        )]))
+    }
+
+    #[test]
+    fn should_read_gql_errors_with_causes() {
+        let cause = BoltMap::from_entries(vec![
+            ("message", "Invalid input 'X'".into()),
+            ("gql_status", "42I06".into()),
+            ("description", "error: syntax error - invalid input".into()),
+        ]);
+        let error = Neo4jError::from_failure(&BoltMap::from_entries(vec![
+            ("neo4j_code", "Neo.ClientError.Statement.SyntaxError".into()),
+            ("message", "Invalid input".into()),
+            ("gql_status", "42001".into()),
+            ("description", "error: syntax error - invalid syntax".into()),
+            ("diagnostic_record", diagnostic_record("CLIENT_ERROR")),
+            ("cause", BoltType::Map(cause)),
+        ]));
+
+        assert_eq!(error.code(), "Neo.ClientError.Statement.SyntaxError");
+        assert_eq!(error.gql_status(), Some("42001"));
+        assert_eq!(
+            error.gql_status_description(),
+            Some("error: syntax error - invalid syntax")
+        );
+        assert_eq!(error.classification(), GqlErrorClassification::ClientError);
+        assert_eq!(
+            error.to_string(),
+            "Neo4j error `Neo.ClientError.Statement.SyntaxError`: Invalid input"
+        );
+
+        let error = Error::Neo4j(error);
+        let cause = std::error::Error::source(&error).unwrap();
+        assert_eq!(cause.to_string(), "Neo4j error `42I06`: Invalid input 'X'");
+        assert!(cause.source().is_none());
+
+        let legacy = Neo4jError::from_failure(&BoltMap::from_entries(vec![
+            ("code", "Neo.ClientError.Statement.SyntaxError".into()),
+            ("message", "Invalid input".into()),
+        ]));
+        assert_eq!(legacy.code(), "Neo.ClientError.Statement.SyntaxError");
+        assert_eq!(legacy.gql_status(), None);
+        assert_eq!(legacy.classification(), GqlErrorClassification::Unknown);
+        assert!(legacy.cause().is_none());
+        assert!(legacy.gql.is_none());
+    }
+
+    #[test]
+    fn should_retry_transient_gql_errors() {
+        let error = |code: &str, classification: &str| {
+            Neo4jError::from_failure(&BoltMap::from_entries(vec![
+                ("neo4j_code", code.into()),
+                ("message", "".into()),
+                ("diagnostic_record", diagnostic_record(classification)),
+            ]))
+        };
+
+        assert!(error(
+            "Neo.TransientError.General.OutOfMemoryError",
+            "TRANSIENT_ERROR"
+        )
+        .can_retry());
+        assert!(error("Neo.DatabaseError.General.UnknownError", "TRANSIENT_ERROR").can_retry());
+        assert!(!error("Neo.DatabaseError.General.UnknownError", "DATABASE_ERROR").can_retry());
+        // the code of client errors takes precedence
+        assert!(!error(
+            "Neo.TransientError.Transaction.Terminated",
+            "TRANSIENT_ERROR"
+        )
+        .can_retry());
+        assert!(error("Neo.ClientError.Cluster.NotALeader", "CLIENT_ERROR").can_retry());
+    }
+
+    #[test]
+    fn should_report_pool_timeouts_as_acquire_timeouts() {
+        use deadpool::managed::{PoolError, TimeoutType};
+
+        let error = Error::from(PoolError::<Error>::Timeout(TimeoutType::Wait));
+        assert!(matches!(error, Error::AcquireTimeout));
+        let error = Error::from(PoolError::Backend(Error::ConnectTimeout));
+        assert!(matches!(error, Error::ConnectTimeout));
+    }
+}
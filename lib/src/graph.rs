@@ -1,10 +1,18 @@
-use std::time::Duration;
+use std::{mem, sync::Arc};
+
+use futures::future::BoxFuture;
 
 use crate::{
-    config::{Config, ConfigBuilder, Database, LiveConfig},
+    auth::AuthToken,
+    bookmarks::{BookmarkManager, DefaultBookmarkManager},
+    config::{Config, ConfigBuilder, Database, LiveConfig, SessionConfig, TxConfig},
     errors::Result,
-    pool::{create_pool, ConnectionPool},
+    home_db::HomeDatabases,
+    metrics::Metrics,
+    pool::{create_pool, ConnectionPoolManager},
     query::Query,
+    result_summary::ResultSummary,
+    session::Session,
     stream::DetachedRowStream,
     txn::Txn,
 };
@@ -15,7 +23,8 @@ use crate::{
 #[derive(Clone)]
 pub struct Graph {
     config: LiveConfig,
-    pool: ConnectionPool,
+    pool: ConnectionPoolManager,
+    home_dbs: Arc<HomeDatabases>,
 }
 
 /// Returns a [`Query`] which provides methods like [`Query::param`] to add parameters to the query
@@ -30,7 +39,11 @@ impl Graph {
     pub async fn connect(config: Config) -> Result<Self> {
         let pool = create_pool(&config).await?;
         let config = config.into_live_config();
-        Ok(Graph { config, pool })
+        Ok(Graph {
+            config,
+            pool,
+            home_dbs: Arc::default(),
+        })
     }
 
     /// Connects to the database with default configurations
@@ -51,9 +64,10 @@ impl Graph {
     /// All queries that needs to be run/executed within the transaction
     /// should be executed using either [`Txn::run`] or [`Txn::execute`]
     ///
-    /// Transactions will not be automatically retried on any failure.
+    /// Transactions will not be automatically retried on any failure,
+    /// use [`Graph::execute_write`] or [`Graph::execute_read`] for that.
     pub async fn start_txn(&self) -> Result<Txn> {
-        self.start_txn_on(self.config.db.clone()).await
+        self.session_on(self.config.db.clone()).start_txn().await
     }
 
     /// Starts a new transaction on the provided database.
@@ -62,12 +76,22 @@ impl Graph {
     ///
     /// Transactions will not be automatically retried on any failure.
     pub async fn start_txn_on(&self, db: impl Into<Database>) -> Result<Txn> {
-        let connection = self.pool.get().await?;
-        Txn::new(db.into(), self.config.fetch_size, connection).await
+        self.session_on(Some(db.into())).start_txn().await
+    }
+
+    /// Starts a new transaction on the configured database with a timeout, metadata,
+    /// or access mode from the given [`TxConfig`].
+    ///
+    /// Transactions will not be automatically retried on any failure.
+    pub async fn start_txn_with(&self, config: TxConfig) -> Result<Txn> {
+        self.session_on(self.config.db.clone())
+            .start_txn_with(config)
+            .await
     }
 
     /// Runs a query on the configured database using a connection from the connection pool,
-    /// It doesn't return any [`RowStream`] as the `run` abstraction discards any stream.
+    /// It doesn't return any [`RowStream`] as the `run` abstraction discards any stream,
+    /// but the [`ResultSummary`] of the query, e.g. to check how many nodes it has created.
     ///
     /// This operation retires the query on certain failures.
     /// All errors with the `Transient` error class as well as a few other error classes are considered retryable.
@@ -77,12 +101,13 @@ impl Graph {
     /// Use [`Graph::run`] for cases where you just want a write operation
     ///
     /// use [`Graph::execute`] when you are interested in the result stream
-    pub async fn run(&self, q: Query) -> Result<()> {
-        self.run_on(&self.config.db, q).await
+    pub async fn run(&self, q: Query) -> Result<ResultSummary> {
+        self.session_on(self.config.db.clone()).run(q).await
     }
 
     /// Runs a query on the provided database using a connection from the connection pool.
-    /// It doesn't return any [`RowStream`] as the `run` abstraction discards any stream.
+    /// It doesn't return any [`RowStream`] as the `run` abstraction discards any stream,
+    /// but the [`ResultSummary`] of the query, e.g. to check how many nodes it has created.
     ///
     /// This operation retires the query on certain failures.
     /// All errors with the `Transient` error class as well as a few other error classes are considered retryable.
@@ -92,20 +117,18 @@ impl Graph {
     /// Use [`Graph::run`] for cases where you just want a write operation
     ///
     /// use [`Graph::execute`] when you are interested in the result stream
-    pub async fn run_on(&self, db: &str, q: Query) -> Result<()> {
-        backoff::future::retry_notify(
-            self.pool.manager().backoff(),
-            || {
-                let pool = &self.pool;
-                let query = &q;
-                async move {
-                    let mut connection = pool.get().await.map_err(crate::Error::from)?;
-                    query.run_retryable(db, &mut connection).await
-                }
-            },
-            Self::log_retry,
-        )
-        .await
+    pub async fn run_on(&self, db: &str, q: Query) -> Result<ResultSummary> {
+        self.session_on(Some(db.into())).run(q).await
+    }
+
+    /// Runs a query on the configured database in an auto-commit transaction
+    /// configured by the given [`TxConfig`].
+    ///
+    /// This operation retries the query on certain failures, see [`Graph::run`].
+    pub async fn run_with(&self, q: Query, config: TxConfig) -> Result<ResultSummary> {
+        self.session_on(self.config.db.clone())
+            .run_with(q, config)
+            .await
     }
 
     /// Executes a query on the configured database and returns a [`DetachedRowStream`]
@@ -115,7 +138,7 @@ impl Graph {
     /// This includes errors during a leader election or when the transaction resources on the server (memory, handles, ...) are exhausted.
     /// Retries happen with an exponential backoff until a retry delay exceeds 60s, at which point the query fails with the last error as it would without any retry.
     pub async fn execute(&self, q: Query) -> Result<DetachedRowStream> {
-        self.execute_on(&self.config.db, q).await
+        self.session_on(self.config.db.clone()).execute(q).await
     }
 
     /// Executes a query on the provided database and returns a [`DetaRowStream`]
@@ -125,29 +148,191 @@ impl Graph {
     /// This includes errors during a leader election or when the transaction resources on the server (memory, handles, ...) are exhausted.
     /// Retries happen with an exponential backoff until a retry delay exceeds 60s, at which point the query fails with the last error as it would without any retry.
     pub async fn execute_on(&self, db: &str, q: Query) -> Result<DetachedRowStream> {
-        backoff::future::retry_notify(
-            self.pool.manager().backoff(),
-            || {
-                let pool = &self.pool;
-                let fetch_size = self.config.fetch_size;
-                let query = &q;
-                async move {
-                    let connection = pool.get().await.map_err(crate::Error::from)?;
-                    query.execute_retryable(db, fetch_size, connection).await
-                }
-            },
-            Self::log_retry,
+        self.session_on(Some(db.into())).execute(q).await
+    }
+
+    /// Executes a query on the configured database in an auto-commit transaction
+    /// configured by the given [`TxConfig`] and returns a [`DetachedRowStream`].
+    ///
+    /// This operation retries the query on certain failures, see [`Graph::execute`].
+    pub async fn execute_with(&self, q: Query, config: TxConfig) -> Result<DetachedRowStream> {
+        self.session_on(self.config.db.clone())
+            .execute_with(q, config)
+            .await
+    }
+
+    /// Runs a query on the configured database as the user of the given [`AuthToken`],
+    /// instead of the user of the [`Config`].
+    ///
+    /// A pooled connection authenticates as that user for this query only,
+    /// which requires Bolt 5.1 or later, see [`SessionConfig::auth`].
+    /// This operation retries the query on certain failures, see [`Graph::run`].
+    pub async fn run_as(&self, auth: AuthToken, q: Query) -> Result<ResultSummary> {
+        self.session_as(auth).run(q).await
+    }
+
+    /// Executes a query on the configured database as the user of the given [`AuthToken`]
+    /// and returns a [`DetachedRowStream`].
+    ///
+    /// A pooled connection authenticates as that user for this query only,
+    /// which requires Bolt 5.1 or later, see [`SessionConfig::auth`].
+    /// This operation retries the query on certain failures, see [`Graph::execute`].
+    pub async fn execute_as(&self, auth: AuthToken, q: Query) -> Result<DetachedRowStream> {
+        self.session_as(auth).execute(q).await
+    }
+
+    /// Runs the given transaction function in a write transaction on the configured database.
+    ///
+    /// The transaction is committed when the function returns `Ok`
+    /// and rolled back when it returns an error.
+    /// If the function or the commit fails with a retryable error, e.g. a `Transient` error
+    /// or because the cluster leader has changed, the function is called again in a new
+    /// transaction on a fresh connection.
+    /// Retries happen with the same exponential backoff as for [`Graph::run`],
+    /// so the function should not have any side effects outside of the transaction.
+    ///
+    /// The function has to return a boxed future, because it borrows the transaction:
+    ///
+    /// ```no_run
+    /// # use neo4rs::*;
+    /// # async fn example(graph: Graph) -> Result<()> {
+    /// let name = "Alice".to_owned();
+    /// let id: i64 = graph
+    ///     .execute_write(|txn| {
+    ///         let name = name.clone();
+    ///         Box::pin(async move {
+    ///             let q = query("CREATE (p:Person {name: $name}) RETURN id(p) AS id");
+    ///             let mut result = txn.execute(q.param("name", name)).await?;
+    ///             let row = result.next(txn.handle()).await?.expect("one row");
+    ///             Ok(row.get("id")?)
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_write<T, F>(&self, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        self.session_on(self.config.db.clone())
+            .execute_write(work)
+            .await
+    }
+
+    /// Runs the given transaction function in a write transaction on the configured database,
+    /// configured by the given [`TxConfig`], e.g. with a timeout or metadata.
+    /// The access mode of the config is ignored.
+    ///
+    /// See [`Graph::execute_write`] for how the transaction is completed and retried.
+    pub async fn execute_write_with<T, F>(&self, config: TxConfig, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        self.session_on(self.config.db.clone())
+            .execute_write_with(config, work)
+            .await
+    }
+
+    /// Runs the given transaction function in a read transaction on the configured database.
+    /// With client-side routing, the transaction is sent to a reader of the cluster.
+    ///
+    /// See [`Graph::execute_write`] for how the transaction is completed and retried.
+    pub async fn execute_read<T, F>(&self, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        self.session_on(self.config.db.clone())
+            .execute_read(work)
+            .await
+    }
+
+    /// Runs the given transaction function in a read transaction on the configured database,
+    /// configured by the given [`TxConfig`], e.g. with a timeout or metadata.
+    /// The access mode of the config is ignored.
+    ///
+    /// See [`Graph::execute_write`] for how the transaction is completed and retried.
+    pub async fn execute_read_with<T, F>(&self, config: TxConfig, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        self.session_on(self.config.db.clone())
+            .execute_read_with(config, work)
+            .await
+    }
+
+    /// Creates a [`Session`] to scope a sequence of transactions to a database,
+    /// an access mode, a chain of bookmarks, or an impersonated user.
+    ///
+    /// Unlike the queries on the [`Graph`], the transactions of a session don't use the
+    /// bookmark manager of the graph, but start from the bookmarks of the [`SessionConfig`].
+    pub fn session(&self, mut config: SessionConfig) -> Session {
+        let bookmarks = mem::take(&mut config.bookmarks);
+        if config.db.is_none() {
+            config.db.clone_from(&self.config.db);
+        }
+        Session::new(
+            self.pool.clone(),
+            &self.home_dbs,
+            config,
+            self.config.fetch_size,
+            Arc::new(DefaultBookmarkManager::with_bookmarks(bookmarks)),
         )
-        .await
     }
 
-    fn log_retry(e: crate::Error, delay: Duration) {
-        let level = match delay.as_millis() {
-            0..=499 => log::Level::Debug,
-            500..=4999 => log::Level::Info,
-            _ => log::Level::Warn,
-        };
-        log::log!(level, "Retrying query in {delay:?} due to error: {e}");
+    /// The session that is used for the queries that are run directly on the graph.
+    fn session_on(&self, db: Option<Database>) -> Session {
+        self.graph_session(SessionConfig {
+            db,
+            ..SessionConfig::default()
+        })
+    }
+
+    fn session_as(&self, auth: AuthToken) -> Session {
+        self.graph_session(SessionConfig {
+            db: self.config.db.clone(),
+            auth: Some(auth),
+            ..SessionConfig::default()
+        })
+    }
+
+    /// A session that shares the bookmark manager of the graph.
+    fn graph_session(&self, config: SessionConfig) -> Session {
+        Session::new(
+            self.pool.clone(),
+            &self.home_dbs,
+            config,
+            self.config.fetch_size,
+            self.config.bookmark_manager.clone(),
+        )
+    }
+
+    /// The manager that keeps track of the bookmarks of this graph.
+    /// Pass it to [`ConfigBuilder::with_bookmark_manager`] to continue the
+    /// causal chain with another [`Graph`].
+    pub fn bookmark_manager(&self) -> Arc<dyn BookmarkManager> {
+        self.config.bookmark_manager.clone()
+    }
+
+    /// A snapshot of the usage of the connection pools and the retries of this graph.
+    ///
+    /// ```no_run
+    /// # async fn example(graph: neo4rs::Graph) {
+    /// let metrics = graph.metrics();
+    /// for pool in metrics.pools() {
+    ///     println!(
+    ///         "{}: {} in use, {} idle, {} acquired in {:?}",
+    ///         pool.address(),
+    ///         pool.in_use(),
+    ///         pool.idle(),
+    ///         pool.acquisition_time().count(),
+    ///         pool.acquisition_time().sum(),
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    pub fn metrics(&self) -> Metrics {
+        Metrics::new(self.pool.pool_metrics(), self.pool.retries())
     }
 }
 
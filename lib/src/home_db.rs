@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use crate::{auth::AuthToken, config::Database, messages::Success};
+
+/// Remembers the home database of every user, so that it is only resolved once
+/// instead of for every session.
+/// The key is the impersonated user, or `None` for the authenticated user.
+/// Since the auth manager may switch to the token of another user at any time,
+/// the home databases are only valid for the token they were resolved with.
+#[derive(Debug, Default)]
+pub(crate) struct HomeDatabases {
+    by_user: RwLock<HashMap<Option<String>, Database>>,
+    token: Mutex<Option<AuthToken>>,
+}
+
+impl HomeDatabases {
+    /// Forgets all home databases if the pool authenticates with another token than before.
+    fn authenticated_with(&self, token: &AuthToken) {
+        let mut current = self.token.lock().unwrap();
+        if current.as_ref() != Some(token) {
+            self.by_user.write().unwrap().clear();
+            *current = Some(token.clone());
+        }
+    }
+
+    fn get(&self, imp_user: Option<&str>) -> Option<Database> {
+        self.by_user
+            .read()
+            .unwrap()
+            .get(&imp_user.map(str::to_owned))
+            .cloned()
+    }
+
+    fn insert(&self, imp_user: Option<&str>, db: Database) {
+        self.by_user
+            .write()
+            .unwrap()
+            .insert(imp_user.map(str::to_owned), db);
+    }
+
+    fn remove(&self, imp_user: Option<&str>) {
+        self.by_user
+            .write()
+            .unwrap()
+            .remove(&imp_user.map(str::to_owned));
+    }
+}
+
+/// The home database of a single session.
+/// Once it is resolved, the session is pinned to it, even if the home database
+/// of the user changes in the meantime.
+#[derive(Debug, Clone)]
+pub(crate) struct HomeDatabase {
+    cache: Arc<HomeDatabases>,
+    imp_user: Option<String>,
+    pinned: Arc<Mutex<Option<Database>>>,
+}
+
+impl HomeDatabase {
+    pub(crate) fn new(cache: Arc<HomeDatabases>, imp_user: Option<String>) -> Self {
+        Self {
+            cache,
+            imp_user,
+            pinned: Arc::default(),
+        }
+    }
+
+    pub(crate) fn imp_user(&self) -> Option<&str> {
+        self.imp_user.as_deref()
+    }
+
+    /// Lets the shared cache know which token the connections of the pool use,
+    /// so that it does not hand out the home database of another user.
+    pub(crate) fn authenticated_with(&self, token: &AuthToken) {
+        self.cache.authenticated_with(token);
+    }
+
+    /// The resolved home database, if it is known already.
+    pub(crate) fn get(&self) -> Option<Database> {
+        let mut pinned = self.pinned.lock().unwrap();
+        if pinned.is_none() {
+            *pinned = self.cache.get(self.imp_user());
+        }
+        pinned.clone()
+    }
+
+    pub(crate) fn resolved(&self, db: Database) {
+        self.cache.insert(self.imp_user(), db.clone());
+        *self.pinned.lock().unwrap() = Some(db);
+    }
+
+    /// Picks up the database from the summary of a query that ran without one.
+    pub(crate) fn update(&self, success: &Success) {
+        if let Ok(db) = success.get::<String>("db") {
+            self.resolved(db.into());
+        }
+    }
+
+    /// Forgets the home database after the server reported that it does not exist anymore.
+    pub(crate) fn invalidate(&self) {
+        self.cache.remove(self.imp_user());
+        *self.pinned.lock().unwrap() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoltMap;
+
+    #[test]
+    fn should_share_resolved_database_per_user() {
+        let cache = Arc::new(HomeDatabases::default());
+        let alice = HomeDatabase::new(cache.clone(), Some("alice".into()));
+        assert_eq!(alice.get(), None);
+
+        alice.update(&Success::new(BoltMap::default()));
+        assert_eq!(alice.get(), None);
+
+        let metadata = BoltMap::from_entries([("db", "tenant1".into())]);
+        alice.update(&Success::new(metadata));
+        assert_eq!(alice.get().as_deref(), Some("tenant1"));
+
+        let other_session = HomeDatabase::new(cache.clone(), Some("alice".into()));
+        assert_eq!(other_session.get().as_deref(), Some("tenant1"));
+
+        let bob = HomeDatabase::new(cache.clone(), Some("bob".into()));
+        assert_eq!(bob.get(), None);
+        let authenticated = HomeDatabase::new(cache.clone(), None);
+        assert_eq!(authenticated.get(), None);
+
+        alice.invalidate();
+        assert_eq!(alice.get(), None);
+        assert_eq!(HomeDatabase::new(cache, Some("alice".into())).get(), None);
+    }
+
+    #[test]
+    fn should_forget_resolved_databases_when_the_token_changes() {
+        let cache = Arc::new(HomeDatabases::default());
+        let session = HomeDatabase::new(cache.clone(), None);
+        session.authenticated_with(&AuthToken::basic("alice", "secret"));
+        session.resolved("alice-db".into());
+
+        let other_session = HomeDatabase::new(cache.clone(), None);
+        other_session.authenticated_with(&AuthToken::basic("alice", "secret"));
+        assert_eq!(other_session.get().as_deref(), Some("alice-db"));
+
+        let other_session = HomeDatabase::new(cache, None);
+        other_session.authenticated_with(&AuthToken::basic("bob", "secret"));
+        assert_eq!(other_session.get(), None);
+        // a session stays pinned to the database it has already resolved
+        assert_eq!(session.get().as_deref(), Some("alice-db"));
+    }
+}
@@ -98,6 +98,28 @@
 //!
 //! ```
 //!
+//! ### Managed transactions
+//!
+//! [`Graph::execute_write`] and [`Graph::execute_read`] run a transaction function that
+//! is committed when it succeeds and rolled back when it fails.
+//! The function is retried in a new transaction on errors that are considered retryable,
+//! e.g. when a cluster member is temporarily unavailable.
+//!
+//! ```no_run
+//! use neo4rs::*;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!    let uri = "127.0.0.1:7687";
+//!    let user = "neo4j";
+//!    let pass = "neo";
+//!    let graph = Graph::new(uri, user, pass).await.unwrap();
+//!
+#![doc = include_str!("../include/managed_transactions.rs")]
+//! }
+//!
+//! ```
+//!
 //! ### Streams within a transaction
 //!
 //! Each [`RowStream`] returned by various execute functions within the same
@@ -424,33 +446,56 @@
 mod auth;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 pub mod bolt;
+mod bookmarks;
 mod config;
 mod connection;
 mod convert;
 mod errors;
 mod graph;
+mod home_db;
 mod messages;
+mod metrics;
+mod notification;
 #[cfg(feature = "unstable-serde-packstream-format")]
 mod packstream;
+mod plan;
 mod pool;
 mod query;
+mod result_summary;
+mod routing;
 mod row;
+mod session;
 mod stream;
 #[cfg(feature = "unstable-streaming-summary")]
 pub mod summary;
+mod telemetry;
+mod tls;
 mod txn;
 mod types;
 mod version;
 
-pub use crate::auth::ClientCertificate;
-pub use crate::config::{Config, ConfigBuilder, Database};
+pub use crate::auth::{
+    AuthManager, AuthToken, ClientCertificate, ClientIdentity, ExpiringAuthManager,
+};
+pub use crate::bookmarks::{BookmarkManager, DefaultBookmarkManager};
+pub use crate::config::{AccessMode, Config, ConfigBuilder, Database, SessionConfig, TxConfig};
 pub use crate::errors::{
-    Error, Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind, Result,
+    Error, GqlErrorClassification, Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind,
+    Neo4jSecurityErrorKind, Result,
 };
 pub use crate::graph::{query, Graph};
+pub use crate::metrics::{Histogram, Metrics, PoolMetrics};
+pub use crate::notification::{
+    GqlStatusObject, InputPosition, Notification, NotificationCategory, NotificationFilter,
+    NotificationSeverity,
+};
+pub use crate::plan::{Plan, ProfiledPlan};
 pub use crate::query::Query;
+pub use crate::result_summary::{Counters, QueryType, ResultSummary};
 pub use crate::row::{Node, Path, Point2D, Point3D, Relation, Row, UnboundedRelation};
+pub use crate::session::Session;
 pub use crate::stream::RowStream;
+pub use crate::tls::{TlsConfig, TlsVersion};
 pub use crate::txn::Txn;
 pub use crate::types::serde::{
     DeError, EndNodeId, Id, Indices, Keys, Labels, Nodes, Offset, Relationships, StartNodeId,
@@ -462,5 +507,12 @@ pub use crate::types::{
     BoltPoint2D, BoltPoint3D, BoltRelation, BoltString, BoltTime, BoltType, BoltUnboundedRelation,
 };
 pub use crate::version::Version;
+/// The `rustls` crate that [`TlsConfig::with_client_config`] expects a config of.
+///
+/// This is rustls 0.23, as used by `tokio-rustls` 0.26.
+/// A config built with another major version of `rustls` is a different type,
+/// so build it with this re-export instead of a direct dependency.
+/// Upgrading `rustls` is a breaking change of this crate.
+pub use tokio_rustls::rustls;
 
 pub(crate) use messages::Success;
@@ -4,15 +4,22 @@ mod commit;
 mod discard;
 mod failure;
 mod hello;
+mod logoff;
+mod logon;
 mod pull;
 mod record;
 mod reset;
 mod rollback;
+mod route;
 mod run;
 mod success;
 
 use crate::{
+    auth::AuthToken,
+    bookmarks,
+    config::{AccessMode, TxConfig},
     errors::{Error, Result},
+    notification::NotificationFilter,
     types::{BoltMap, BoltWireFormat},
     version::Version,
     BoltString, BoltType,
@@ -23,10 +30,13 @@ use commit::Commit;
 use discard::Discard;
 use failure::Failure;
 use hello::Hello;
+use logoff::Logoff;
+use logon::Logon;
 use pull::Pull;
 use record::Record;
 use reset::Reset;
 use rollback::Rollback;
+use route::Route;
 use run::Run;
 pub(crate) use success::Success;
 
@@ -47,23 +57,26 @@ pub enum BoltRequest {
     Commit(Commit),
     Rollback(Rollback),
     Reset(Reset),
+    Route(Route),
+    Logon(Logon),
+    Logoff(Logoff),
 }
 
 pub struct HelloBuilder {
     agent: BoltString,
-    principal: BoltString,
-    credentials: BoltString,
+    auth: AuthToken,
     routing: Option<BoltMap>,
+    notification_filter: NotificationFilter,
     version: Version,
 }
 
 impl HelloBuilder {
-    pub fn new(principal: impl Into<BoltString>, credentials: impl Into<BoltString>) -> Self {
+    pub fn new(auth: AuthToken) -> Self {
         Self {
             agent: "neo4rs".into(),
-            principal: principal.into(),
-            credentials: credentials.into(),
+            auth,
             routing: None,
+            notification_filter: NotificationFilter::default(),
             version: Version::V4,
         }
     }
@@ -72,58 +85,126 @@ impl HelloBuilder {
         self.routing = Some(routing);
     }
 
+    pub fn with_notification_filter(&mut self, notification_filter: NotificationFilter) {
+        self.notification_filter = notification_filter;
+    }
+
     pub fn version(self, version: Version) -> Self {
         Self { version, ..self }
     }
 
+    /// Rejects a notification filter that the server would silently ignore.
+    pub fn check_version(&self) -> Result<()> {
+        self.notification_filter.check_version(self.version)
+    }
+
+    /// Since Bolt 5.1, the credentials are sent in a separate LOGON message after the HELLO.
+    pub fn logon(&self) -> Option<BoltRequest> {
+        (self.version >= Version::V5_1).then(|| BoltRequest::logon(&self.auth))
+    }
+
     pub fn build(self) -> BoltRequest {
         let HelloBuilder {
             agent,
-            principal,
-            credentials,
+            auth,
             routing,
+            notification_filter,
             version,
         } = self;
-        BoltRequest::hello(agent, principal, credentials, routing, version)
+        BoltRequest::hello(agent, &auth, routing, &notification_filter, version)
     }
 }
 
+fn bolt_agent() -> BoltMap {
+    let mut agent = BoltMap::default();
+    agent.put(
+        "product".into(),
+        concat!("neo4rs/", env!("CARGO_PKG_VERSION")).into(),
+    );
+    agent.put("language".into(), "Rust".into());
+    agent.put(
+        "platform".into(),
+        format!("{}; {}", std::env::consts::OS, std::env::consts::ARCH).into(),
+    );
+    agent
+}
+
 impl BoltRequest {
     pub fn hello(
         agent: BoltString,
-        principal: BoltString,
-        credentials: BoltString,
+        auth: &AuthToken,
         routing: Option<BoltMap>,
+        notification_filter: &NotificationFilter,
         version: Version,
     ) -> BoltRequest {
         let mut data = BoltMap::default();
         data.put("user_agent".into(), BoltType::String(agent));
-        data.put("scheme".into(), "basic".into());
-        data.put("principal".into(), BoltType::String(principal));
-        data.put("credentials".into(), BoltType::String(credentials));
+        if version < Version::V5_1 {
+            for (key, value) in auth.entries() {
+                data.put(key.into(), value);
+            }
+        }
+        if version >= Version::V5_3 {
+            data.put("bolt_agent".into(), BoltType::Map(bolt_agent()));
+        }
         if version >= Version::V4_1 {
             if let Some(context) = routing {
                 data.put("routing".into(), BoltType::Map(context));
             }
         }
+        if version >= Version::V5_2 {
+            for (key, value) in notification_filter.entries(version) {
+                data.put(key.into(), value);
+            }
+        }
         BoltRequest::Hello(Hello::new(data))
     }
 
-    pub fn run(db: &str, query: &str, params: BoltMap) -> BoltRequest {
-        BoltRequest::Run(Run::new(db.into(), query.into(), params))
+    pub fn logon(auth: &AuthToken) -> BoltRequest {
+        BoltRequest::Logon(Logon::new(auth.to_bolt()))
+    }
+
+    pub fn logoff() -> BoltRequest {
+        BoltRequest::Logoff(Logoff::new())
+    }
+
+    pub fn run(db: Option<&str>, query: &str, params: BoltMap) -> BoltRequest {
+        match db {
+            Some(db) => BoltRequest::Run(Run::new(db.into(), query.into(), params)),
+            None => BoltRequest::Run(Run::with_extra(query.into(), params, BoltMap::default())),
+        }
+    }
+
+    /// Runs a query in an auto-commit transaction.
+    pub fn run_auto_commit(
+        db: Option<&str>,
+        query: &str,
+        params: BoltMap,
+        bookmarks: &[String],
+        config: &TxConfig,
+        version: Version,
+    ) -> BoltRequest {
+        let extra = tx_extra(db, bookmarks, config, version);
+        BoltRequest::Run(Run::with_extra(query.into(), params, extra))
     }
 
     pub fn pull(n: usize, qid: i64) -> BoltRequest {
         BoltRequest::Pull(Pull::new(n as i64, qid))
     }
 
-    pub fn discard() -> BoltRequest {
-        BoltRequest::Discard(Discard::default())
+    /// Discards all remaining records of the result with the given id,
+    /// or of the last result for `-1`.
+    pub fn discard(qid: i64) -> BoltRequest {
+        BoltRequest::Discard(Discard::new(-1, qid))
     }
 
-    pub fn begin(db: &str) -> BoltRequest {
-        let begin = Begin::new([("db".into(), db.into())].into_iter().collect());
-        BoltRequest::Begin(begin)
+    pub fn begin(
+        db: Option<&str>,
+        bookmarks: &[String],
+        config: &TxConfig,
+        version: Version,
+    ) -> BoltRequest {
+        BoltRequest::Begin(Begin::new(tx_extra(db, bookmarks, config, version)))
     }
 
     pub fn commit() -> BoltRequest {
@@ -137,6 +218,117 @@ impl BoltRequest {
     pub fn reset() -> BoltRequest {
         BoltRequest::Reset(Reset::new())
     }
+
+    pub fn route(
+        routing: BoltMap,
+        bookmarks: &[String],
+        db: Option<&str>,
+        imp_user: Option<&str>,
+        version: Version,
+    ) -> BoltRequest {
+        let extra = if version >= Version::V4_4 {
+            let mut extra = BoltMap::default();
+            if let Some(db) = db {
+                extra.put("db".into(), db.into());
+            }
+            if let Some(imp_user) = imp_user {
+                extra.put("imp_user".into(), imp_user.into());
+            }
+            BoltType::Map(extra)
+        } else {
+            db.into()
+        };
+        BoltRequest::Route(Route::new(routing, bookmarks::to_bolt(bookmarks), extra))
+    }
+}
+
+/// The extra map of BEGIN and of RUN in an auto-commit transaction.
+/// Without a database, the server uses the home database of the user.
+fn tx_extra(
+    db: Option<&str>,
+    bookmarks: &[String],
+    config: &TxConfig,
+    version: Version,
+) -> BoltMap {
+    let mut extra = BoltMap::default();
+    if let Some(db) = db {
+        extra.put("db".into(), db.into());
+    }
+    if !bookmarks.is_empty() {
+        extra.put(
+            "bookmarks".into(),
+            BoltType::List(bookmarks::to_bolt(bookmarks)),
+        );
+    }
+    if let Some(timeout) = config.timeout_millis() {
+        extra.put("tx_timeout".into(), timeout.into());
+    }
+    if !config.metadata.value.is_empty() {
+        extra.put("tx_metadata".into(), BoltType::Map(config.metadata.clone()));
+    }
+    if config.mode() == AccessMode::Read {
+        extra.put("mode".into(), "r".into());
+    }
+    if let Some(imp_user) = &config.imp_user {
+        extra.put("imp_user".into(), imp_user.as_str().into());
+    }
+    if let Some(filter) = &config.notification_filter {
+        for (key, value) in filter.entries(version) {
+            extra.put(key.into(), value);
+        }
+    }
+    extra
+}
+
+/// What the spans of the `tracing` feature record about a request.
+/// Credentials, parameters and metadata are never recorded.
+#[cfg(feature = "tracing")]
+impl BoltRequest {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            BoltRequest::Hello(_) => "HELLO",
+            BoltRequest::Run(_) => "RUN",
+            BoltRequest::Pull(_) => "PULL",
+            BoltRequest::Discard(_) => "DISCARD",
+            BoltRequest::Begin(_) => "BEGIN",
+            BoltRequest::Commit(_) => "COMMIT",
+            BoltRequest::Rollback(_) => "ROLLBACK",
+            BoltRequest::Reset(_) => "RESET",
+            BoltRequest::Route(_) => "ROUTE",
+            BoltRequest::Logon(_) => "LOGON",
+            BoltRequest::Logoff(_) => "LOGOFF",
+        }
+    }
+
+    /// The database of RUN and BEGIN, if it is not the home database of the user.
+    pub(crate) fn db(&self) -> Option<&str> {
+        let extra = match self {
+            BoltRequest::Run(run) => run.extra(),
+            BoltRequest::Begin(begin) => begin.extra(),
+            _ => return None,
+        };
+        match extra.value.get("db") {
+            Some(BoltType::String(db)) => Some(&db.value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn query(&self) -> Option<&str> {
+        match self {
+            BoltRequest::Run(run) => Some(run.query()),
+            _ => None,
+        }
+    }
+
+    /// The id of the result that PULL and DISCARD refer to, unless they refer to the last one.
+    pub(crate) fn qid(&self) -> Option<i64> {
+        let extra = match self {
+            BoltRequest::Pull(pull) => pull.extra(),
+            BoltRequest::Discard(discard) => discard.extra(),
+            _ => return None,
+        };
+        extra.get::<i64>("qid").ok().filter(|qid| *qid >= 0)
+    }
 }
 
 impl BoltRequest {
@@ -150,6 +342,9 @@ impl BoltRequest {
             BoltRequest::Commit(commit) => commit.into_bytes(version)?,
             BoltRequest::Rollback(rollback) => rollback.into_bytes(version)?,
             BoltRequest::Reset(reset) => reset.into_bytes(version)?,
+            BoltRequest::Route(route) => route.into_bytes(version)?,
+            BoltRequest::Logon(logon) => logon.into_bytes(version)?,
+            BoltRequest::Logoff(logoff) => logoff.into_bytes(version)?,
         };
         Ok(bytes)
     }
@@ -11,13 +11,24 @@ impl Begin {
     pub fn new(extra: BoltMap) -> Begin {
         Begin { extra }
     }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn extra(&self) -> &BoltMap {
+        &self.extra
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::version::Version;
+    use crate::{
+        config::{AccessMode, TxConfig},
+        messages::BoltRequest,
+        version::Version,
+        NotificationCategory, NotificationFilter, NotificationSeverity,
+    };
     use bytes::*;
+    use std::time::Duration;
 
     #[test]
     fn should_serialize_begin() {
@@ -52,4 +63,105 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn should_add_tx_config_to_begin() {
+        let config = TxConfig::new()
+            .timeout(Duration::from_millis(1500))
+            .metadata("app", "test")
+            .access_mode(AccessMode::Read);
+        let BoltRequest::Begin(begin) =
+            BoltRequest::begin(Some("neo4j"), &["bm:1".into()], &config, Version::V5_0)
+        else {
+            panic!("expected BEGIN");
+        };
+
+        assert_eq!(begin.extra.get::<String>("db").unwrap(), "neo4j");
+        assert_eq!(
+            begin.extra.get::<Vec<String>>("bookmarks").unwrap(),
+            ["bm:1"]
+        );
+        assert_eq!(begin.extra.get::<i64>("tx_timeout").unwrap(), 1500);
+        assert_eq!(
+            begin
+                .extra
+                .get::<BoltMap>("tx_metadata")
+                .unwrap()
+                .get::<String>("app")
+                .unwrap(),
+            "test"
+        );
+        assert_eq!(begin.extra.get::<String>("mode").unwrap(), "r");
+
+        let BoltRequest::Begin(begin) =
+            BoltRequest::begin(Some("neo4j"), &[], &TxConfig::default(), Version::V5_0)
+        else {
+            panic!("expected BEGIN");
+        };
+        assert_eq!(begin.extra.value.len(), 1);
+
+        let BoltRequest::Begin(begin) =
+            BoltRequest::begin(None, &[], &TxConfig::default(), Version::V5_0)
+        else {
+            panic!("expected BEGIN");
+        };
+        assert!(begin.extra.value.is_empty());
+    }
+
+    #[test]
+    fn should_add_imp_user_to_begin() {
+        let mut config = TxConfig::new();
+        config.imp_user = Some("jane".into());
+        let BoltRequest::Begin(begin) =
+            BoltRequest::begin(Some("neo4j"), &[], &config, Version::V5_0)
+        else {
+            panic!("expected BEGIN");
+        };
+
+        assert_eq!(begin.extra.get::<String>("imp_user").unwrap(), "jane");
+        assert!(!begin.extra.value.contains_key("mode"));
+    }
+
+    #[test]
+    fn should_add_notification_filter_to_begin() {
+        let filter = NotificationFilter::new()
+            .minimum_severity(NotificationSeverity::Warning)
+            .disable_categories([NotificationCategory::Hint]);
+        let config = TxConfig::new().notification_filter(filter);
+
+        let BoltRequest::Begin(begin) = BoltRequest::begin(None, &[], &config, Version::V5_2)
+        else {
+            panic!("expected BEGIN");
+        };
+        assert_eq!(
+            begin
+                .extra
+                .get::<String>("notifications_minimum_severity")
+                .unwrap(),
+            "WARNING"
+        );
+        assert_eq!(
+            begin
+                .extra
+                .get::<Vec<String>>("notifications_disabled_categories")
+                .unwrap(),
+            ["HINT"]
+        );
+
+        let BoltRequest::Begin(begin) = BoltRequest::begin(None, &[], &config, Version::V5_5)
+        else {
+            panic!("expected BEGIN");
+        };
+        assert!(!begin
+            .extra
+            .value
+            .contains_key("notifications_disabled_categories"));
+        assert_eq!(
+            begin
+                .extra
+                .get::<Vec<String>>("notifications_disabled_classifications")
+                .unwrap(),
+            ["HINT"]
+        );
+    }
 }
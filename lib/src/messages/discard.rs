@@ -20,6 +20,11 @@ impl Discard {
         extra.put("qid".into(), qid.into());
         Discard { extra }
     }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn extra(&self) -> &BoltMap {
+        &self.extra
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,6 @@
 use crate::{
     errors::Neo4jError,
     types::{serde::DeError, BoltMap},
-    BoltType,
 };
 use ::serde::Deserialize;
 use neo4rs_macros::BoltStruct;
@@ -21,13 +20,7 @@ impl Failure {
     }
 
     pub(crate) fn into_error(self) -> Neo4jError {
-        let mut meta = self.metadata.value;
-        let (code, message) = (meta.remove("code"), meta.remove("message"));
-        let (code, message) = match (code, message) {
-            (Some(BoltType::String(s)), Some(BoltType::String(m))) => (s.value, m.value),
-            _ => (String::new(), String::new()),
-        };
-        Neo4jError::new(code, message)
+        Neo4jError::from_failure(&self.metadata)
     }
 }
 
@@ -16,7 +16,12 @@ impl Hello {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::version::Version;
+    use crate::{
+        auth::AuthToken,
+        messages::{BoltRequest, HelloBuilder},
+        version::Version,
+        Error, NotificationFilter,
+    };
     use bytes::*;
 
     #[test]
@@ -51,4 +56,90 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn should_move_credentials_to_logon_since_v5_1() {
+        let auth = AuthToken::basic("user", "pass");
+        let BoltRequest::Hello(hello) = BoltRequest::hello(
+            "neo4rs".into(),
+            &auth,
+            None,
+            &Default::default(),
+            Version::V5_0,
+        ) else {
+            panic!("expected HELLO");
+        };
+        assert!(hello.extra.get::<String>("credentials").is_ok());
+        assert!(hello.extra.get::<BoltMap>("bolt_agent").is_err());
+
+        let auth = AuthToken::basic("user", "pass");
+        let BoltRequest::Hello(hello) = BoltRequest::hello(
+            "neo4rs".into(),
+            &auth,
+            None,
+            &Default::default(),
+            Version::V5_3,
+        ) else {
+            panic!("expected HELLO");
+        };
+        assert!(hello.extra.get::<String>("credentials").is_err());
+        assert!(hello.extra.get::<String>("scheme").is_err());
+        let agent = hello.extra.get::<BoltMap>("bolt_agent").unwrap();
+        assert_eq!(agent.get::<String>("language").unwrap(), "Rust");
+    }
+
+    #[test]
+    fn should_send_notification_filter_since_v5_2() {
+        let auth = AuthToken::none();
+        let filter = NotificationFilter::disabled();
+        let BoltRequest::Hello(hello) =
+            BoltRequest::hello("neo4rs".into(), &auth, None, &filter, Version::V5_2)
+        else {
+            panic!("expected HELLO");
+        };
+        assert_eq!(
+            hello
+                .extra
+                .get::<String>("notifications_minimum_severity")
+                .unwrap(),
+            "OFF"
+        );
+        assert!(!hello
+            .extra
+            .value
+            .contains_key("notifications_disabled_categories"));
+
+        let builder = |filter: NotificationFilter, version| {
+            let mut builder = HelloBuilder::new(AuthToken::none());
+            builder.with_notification_filter(filter);
+            builder.version(version)
+        };
+        assert!(builder(filter.clone(), Version::V5_2)
+            .check_version()
+            .is_ok());
+        assert!(matches!(
+            builder(filter, Version::V5_1).check_version(),
+            Err(Error::NotificationFilterNotSupported(_))
+        ));
+        assert!(builder(NotificationFilter::new(), Version::V4_4)
+            .check_version()
+            .is_ok());
+    }
+
+    #[test]
+    fn should_send_auth_token() {
+        let auth = AuthToken::bearer("token");
+        let BoltRequest::Hello(hello) = BoltRequest::hello(
+            "neo4rs".into(),
+            &auth,
+            None,
+            &Default::default(),
+            Version::V4_4,
+        ) else {
+            panic!("expected HELLO");
+        };
+        assert_eq!(hello.extra.get::<String>("scheme").unwrap(), "bearer");
+        assert_eq!(hello.extra.get::<String>("credentials").unwrap(), "token");
+        assert!(hello.extra.get::<String>("principal").is_err());
+    }
 }
@@ -0,0 +1,33 @@
+use neo4rs_macros::BoltStruct;
+
+#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
+#[signature(0xB0, 0x6B)]
+pub struct Logoff;
+
+impl Logoff {
+    pub fn new() -> Logoff {
+        Logoff
+    }
+}
+
+impl Default for Logoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::BoltWireFormat, version::Version};
+    use bytes::*;
+
+    #[test]
+    fn should_serialize_logoff() {
+        let logoff = Logoff::new();
+
+        let bytes: Bytes = logoff.into_bytes(Version::V5_1).unwrap();
+
+        assert_eq!(bytes, Bytes::from_static(&[0xB0, 0x6B,]));
+    }
+}
@@ -20,6 +20,11 @@ impl Pull {
         extra.put("qid".into(), qid.into());
         Pull { extra }
     }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn extra(&self) -> &BoltMap {
+        &self.extra
+    }
 }
 
 #[cfg(test)]
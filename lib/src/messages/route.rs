@@ -0,0 +1,98 @@
+use crate::types::*;
+use neo4rs_macros::BoltStruct;
+
+#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[signature(0xB3, 0x66)]
+pub struct Route {
+    routing: BoltMap,
+    bookmarks: BoltList,
+    extra: BoltType,
+}
+
+impl Route {
+    /// `extra` is the database name (or null) in Bolt 4.3
+    /// and a map with an optional `db` entry since Bolt 4.4.
+    pub fn new(routing: BoltMap, bookmarks: BoltList, extra: BoltType) -> Route {
+        Route {
+            routing,
+            bookmarks,
+            extra,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{messages::BoltRequest, version::Version};
+    use bytes::*;
+
+    #[test]
+    fn should_serialize_route() {
+        let route = Route::new(
+            BoltMap::from_entries([("region", "eu".into())]),
+            BoltList::default(),
+            BoltType::String("db".into()),
+        );
+
+        let bytes: Bytes = route.into_bytes(Version::V4_3).unwrap();
+
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[
+                0xB3,
+                0x66,
+                map::TINY | 1,
+                string::TINY | 6,
+                b'r',
+                b'e',
+                b'g',
+                b'i',
+                b'o',
+                b'n',
+                string::TINY | 2,
+                b'e',
+                b'u',
+                list::TINY,
+                string::TINY | 2,
+                b'd',
+                b'b',
+            ])
+        );
+    }
+
+    #[test]
+    fn should_serialize_route_with_extra_map() {
+        let route = Route::new(
+            BoltMap::default(),
+            BoltList::default(),
+            BoltType::Map(BoltMap::default()),
+        );
+
+        let bytes: Bytes = route.into_bytes(Version::V4_4).unwrap();
+
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[0xB3, 0x66, map::TINY, list::TINY, map::TINY])
+        );
+    }
+
+    #[test]
+    fn should_add_bookmarks_to_route() {
+        let bookmarks = ["bm:1".to_owned(), "bm:2".to_owned()];
+        let BoltRequest::Route(route) = BoltRequest::route(
+            BoltMap::default(),
+            &bookmarks,
+            Some("neo4j"),
+            None,
+            Version::V4_4,
+        ) else {
+            panic!("expected ROUTE");
+        };
+
+        assert_eq!(
+            route.bookmarks,
+            BoltList::from(vec![BoltType::from("bm:1"), BoltType::from("bm:2")])
+        );
+    }
+}
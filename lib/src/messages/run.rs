@@ -19,6 +19,24 @@ impl Run {
                 .collect(),
         }
     }
+
+    pub fn with_extra(query: BoltString, parameters: BoltMap, extra: BoltMap) -> Run {
+        Run {
+            query,
+            parameters,
+            extra,
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn query(&self) -> &str {
+        &self.query.value
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn extra(&self) -> &BoltMap {
+        &self.extra
+    }
 }
 
 #[cfg(test)]
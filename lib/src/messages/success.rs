@@ -9,6 +9,11 @@ pub struct Success {
 }
 
 impl Success {
+    #[cfg(test)]
+    pub(crate) fn new(metadata: BoltMap) -> Self {
+        Success { metadata }
+    }
+
     pub fn get<'this, T>(&'this self, key: &str) -> Result<T, DeError>
     where
         T: Deserialize<'this>,
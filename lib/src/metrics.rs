@@ -0,0 +1,296 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The upper bounds of the buckets of the acquisition time histogram.
+const ACQUISITION_BUCKETS: [Duration; 10] = [
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+    Duration::from_secs(30),
+];
+
+/// A snapshot of the connection pools of a [`crate::Graph`], see [`crate::Graph::metrics`].
+///
+/// A direct connection (`bolt://`) has a single pool,
+/// a routed connection (`neo4j://`) has one pool for every server of the routing tables.
+///
+/// With the `metrics` feature, the same counters and timings are also recorded with the
+/// [`metrics`](https://docs.rs/metrics) crate as they happen, labelled with the `address`
+/// of the pool, so that they can be exported e.g. to Prometheus:
+/// * `neo4rs_connections_created_total`
+/// * `neo4rs_connections_closed_total`
+/// * `neo4rs_connections_failed_total`
+/// * `neo4rs_connection_acquisition_seconds` (histogram)
+/// * `neo4rs_retries_total` (without a label)
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    pools: Vec<PoolMetrics>,
+    retries: u64,
+}
+
+impl Metrics {
+    pub(crate) fn new(pools: Vec<PoolMetrics>, retries: u64) -> Self {
+        Self { pools, retries }
+    }
+
+    /// The metrics of every connection pool.
+    pub fn pools(&self) -> &[PoolMetrics] {
+        &self.pools
+    }
+
+    /// The metrics of the connection pool for the given address, e.g. `localhost:7687`.
+    pub fn pool(&self, address: &str) -> Option<&PoolMetrics> {
+        self.pools.iter().find(|pool| pool.address == address)
+    }
+
+    /// How often queries and transactions have been retried after a retryable error.
+    pub fn retries(&self) -> u64 {
+        self.retries
+    }
+}
+
+/// A snapshot of one connection pool, see [`Metrics`].
+#[derive(Debug, Clone)]
+pub struct PoolMetrics {
+    address: String,
+    in_use: usize,
+    idle: usize,
+    max_size: usize,
+    created: u64,
+    closed: u64,
+    failed: u64,
+    acquisition_time: Histogram,
+}
+
+impl PoolMetrics {
+    /// The address of the server the connections of this pool are connected to.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// The number of connections that are currently handed out.
+    pub fn in_use(&self) -> usize {
+        self.in_use
+    }
+
+    /// The number of open connections that are currently waiting in the pool.
+    pub fn idle(&self) -> usize {
+        self.idle
+    }
+
+    /// The maximum number of connections of the pool.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// The number of connections that have been opened.
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /// The number of connections that have been closed, e.g. because they were broken,
+    /// exceeded their lifetime or were idle for too long.
+    pub fn closed(&self) -> u64 {
+        self.closed
+    }
+
+    /// The number of connections that could not be opened.
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+
+    /// How long it took to acquire a connection from the pool, including opening a new one.
+    pub fn acquisition_time(&self) -> &Histogram {
+        &self.acquisition_time
+    }
+}
+
+/// The distribution of durations, see [`PoolMetrics::acquisition_time`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    buckets: Vec<(Duration, u64)>,
+    count: u64,
+    sum: Duration,
+}
+
+impl Histogram {
+    /// The number of durations per bucket, with the upper bound of each bucket.
+    ///
+    /// Every duration is only counted in the first bucket it fits in,
+    /// the last bucket with an upper bound of [`Duration::MAX`] holds the rest.
+    pub fn buckets(&self) -> &[(Duration, u64)] {
+        &self.buckets
+    }
+
+    /// The number of recorded durations.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The sum of all recorded durations.
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+}
+
+/// Counts the events of one connection pool.
+#[derive(Debug)]
+pub(crate) struct PoolCounters {
+    address: String,
+    created: AtomicU64,
+    closed: AtomicU64,
+    failed: AtomicU64,
+    acquisition_buckets: [AtomicU64; ACQUISITION_BUCKETS.len() + 1],
+    acquisition_nanos: AtomicU64,
+}
+
+impl PoolCounters {
+    pub(crate) fn new(address: String) -> Self {
+        Self {
+            address,
+            created: AtomicU64::new(0),
+            closed: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            acquisition_buckets: Default::default(),
+            acquisition_nanos: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn connection_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("neo4rs_connections_created_total", "address" => self.address.clone())
+            .increment(1);
+    }
+
+    pub(crate) fn connection_closed(&self) {
+        self.closed.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("neo4rs_connections_closed_total", "address" => self.address.clone())
+            .increment(1);
+    }
+
+    pub(crate) fn connection_failed(&self) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("neo4rs_connections_failed_total", "address" => self.address.clone())
+            .increment(1);
+    }
+
+    pub(crate) fn connection_acquired(&self, duration: Duration) {
+        let bucket = ACQUISITION_BUCKETS
+            .iter()
+            .position(|bound| duration <= *bound)
+            .unwrap_or(ACQUISITION_BUCKETS.len());
+        self.acquisition_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.acquisition_nanos.fetch_add(nanos, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::histogram!("neo4rs_connection_acquisition_seconds", "address" => self.address.clone())
+            .record(duration.as_secs_f64());
+    }
+
+    /// Takes a snapshot of the counters together with the current size of the pool.
+    pub(crate) fn snapshot(&self, status: deadpool::Status) -> PoolMetrics {
+        let buckets = ACQUISITION_BUCKETS
+            .iter()
+            .chain([&Duration::MAX])
+            .zip(&self.acquisition_buckets)
+            .map(|(bound, count)| (*bound, count.load(Ordering::Relaxed)))
+            .collect::<Vec<_>>();
+        let count = buckets.iter().map(|(_, count)| count).sum();
+        PoolMetrics {
+            address: self.address.clone(),
+            in_use: status.size - status.available,
+            idle: status.available,
+            max_size: status.max_size,
+            created: self.created.load(Ordering::Relaxed),
+            closed: self.closed.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            acquisition_time: Histogram {
+                buckets,
+                count,
+                sum: Duration::from_nanos(self.acquisition_nanos.load(Ordering::Relaxed)),
+            },
+        }
+    }
+}
+
+/// Counts the retries of all sessions of a [`crate::Graph`].
+#[derive(Debug, Default)]
+pub(crate) struct RetryCounter(AtomicU64);
+
+impl RetryCounter {
+    pub(crate) fn retried(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("neo4rs_retries_total").increment(1);
+    }
+
+    pub(crate) fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(size: usize, available: usize) -> deadpool::Status {
+        deadpool::Status {
+            max_size: 16,
+            size,
+            available,
+            waiting: 0,
+        }
+    }
+
+    #[test]
+    fn should_count_pool_events() {
+        let counters = PoolCounters::new("localhost:7687".into());
+        counters.connection_created();
+        counters.connection_created();
+        counters.connection_closed();
+        counters.connection_failed();
+
+        let metrics = counters.snapshot(status(3, 1));
+        assert_eq!(metrics.address(), "localhost:7687");
+        assert_eq!(metrics.in_use(), 2);
+        assert_eq!(metrics.idle(), 1);
+        assert_eq!(metrics.max_size(), 16);
+        assert_eq!(metrics.created(), 2);
+        assert_eq!(metrics.closed(), 1);
+        assert_eq!(metrics.failed(), 1);
+        assert_eq!(metrics.acquisition_time().count(), 0);
+    }
+
+    #[test]
+    fn should_record_acquisition_times_in_buckets() {
+        let counters = PoolCounters::new("localhost:7687".into());
+        counters.connection_acquired(Duration::from_micros(10));
+        counters.connection_acquired(Duration::from_millis(1));
+        counters.connection_acquired(Duration::from_millis(7));
+        counters.connection_acquired(Duration::from_secs(60));
+
+        let histogram = counters.snapshot(status(0, 0)).acquisition_time;
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(
+            histogram.sum(),
+            Duration::from_micros(10) + Duration::from_millis(8) + Duration::from_secs(60)
+        );
+        let buckets = histogram.buckets();
+        assert_eq!(buckets.len(), ACQUISITION_BUCKETS.len() + 1);
+        assert_eq!(buckets[0], (Duration::from_millis(1), 2));
+        assert_eq!(buckets[1], (Duration::from_millis(5), 0));
+        assert_eq!(buckets[2], (Duration::from_millis(10), 1));
+        assert_eq!(buckets[ACQUISITION_BUCKETS.len()], (Duration::MAX, 1));
+    }
+}
@@ -0,0 +1,577 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    errors::{Error, Result},
+    result_summary::ResultSummary,
+    version::Version,
+    BoltMap, BoltType,
+};
+
+/// A warning or a hint that the server reports about a query,
+/// e.g. about deprecated syntax or a cartesian product, see [`ResultSummary::notifications`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    code: String,
+    title: String,
+    description: String,
+    severity: NotificationSeverity,
+    category: NotificationCategory,
+    position: Option<InputPosition>,
+}
+
+impl Notification {
+    /// Reads a notification as sent before Bolt 5.5.
+    fn parse(notification: &BoltMap) -> Option<Self> {
+        Some(Self {
+            code: notification.get::<String>("code").ok()?,
+            title: notification.get::<String>("title").unwrap_or_default(),
+            description: notification
+                .get::<String>("description")
+                .unwrap_or_default(),
+            severity: NotificationSeverity::parse(notification.get::<&str>("severity").ok()),
+            category: NotificationCategory::parse(notification.get::<&str>("category").ok()),
+            position: notification
+                .get::<BoltMap>("position")
+                .ok()
+                .map(|position| InputPosition::parse(&position)),
+        })
+    }
+
+    /// Reads the notification that a GQL status object stands for, since Bolt 5.5.
+    fn from_status(status: &GqlStatusObject, raw: &BoltMap) -> Option<Self> {
+        Some(Self {
+            code: raw.get::<String>("neo4j_code").ok()?,
+            title: raw.get::<String>("title").unwrap_or_default(),
+            description: raw
+                .get::<String>("description")
+                .unwrap_or_else(|_| status.status_description.clone()),
+            severity: status.severity(),
+            category: status.classification(),
+            position: status.position(),
+        })
+    }
+
+    /// The Neo4j status code, e.g. `Neo.ClientNotification.Statement.FeatureDeprecationWarning`.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn severity(&self) -> NotificationSeverity {
+        self.severity
+    }
+
+    pub fn category(&self) -> NotificationCategory {
+        self.category
+    }
+
+    /// Where in the query the notification applies, if it applies to a specific part of it.
+    pub fn position(&self) -> Option<InputPosition> {
+        self.position
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.title)?;
+        if !self.description.is_empty() {
+            write!(f, " ({})", self.description)?;
+        }
+        if let Some(position) = self.position {
+            write!(f, " at {position}")?;
+        }
+        Ok(())
+    }
+}
+
+/// How severe a [`Notification`] or a [`GqlStatusObject`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NotificationSeverity {
+    Warning,
+    Information,
+    /// A severity that this version of the driver does not know.
+    Unknown,
+}
+
+impl NotificationSeverity {
+    fn parse(severity: Option<&str>) -> Self {
+        match severity {
+            Some("WARNING") => NotificationSeverity::Warning,
+            Some("INFORMATION") => NotificationSeverity::Information,
+            _ => NotificationSeverity::Unknown,
+        }
+    }
+}
+
+/// What a [`Notification`] or a [`GqlStatusObject`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NotificationCategory {
+    Hint,
+    Unrecognized,
+    Unsupported,
+    Performance,
+    Deprecation,
+    Security,
+    Topology,
+    Generic,
+    Schema,
+    /// A category that this version of the driver does not know, or no category at all.
+    Unknown,
+}
+
+impl NotificationCategory {
+    fn parse(category: Option<&str>) -> Self {
+        match category {
+            Some("HINT") => NotificationCategory::Hint,
+            Some("UNRECOGNIZED") => NotificationCategory::Unrecognized,
+            Some("UNSUPPORTED") => NotificationCategory::Unsupported,
+            Some("PERFORMANCE") => NotificationCategory::Performance,
+            Some("DEPRECATION") => NotificationCategory::Deprecation,
+            Some("SECURITY") => NotificationCategory::Security,
+            Some("TOPOLOGY") => NotificationCategory::Topology,
+            Some("GENERIC") => NotificationCategory::Generic,
+            Some("SCHEMA") => NotificationCategory::Schema,
+            _ => NotificationCategory::Unknown,
+        }
+    }
+
+    fn as_str(self) -> Option<&'static str> {
+        match self {
+            NotificationCategory::Hint => Some("HINT"),
+            NotificationCategory::Unrecognized => Some("UNRECOGNIZED"),
+            NotificationCategory::Unsupported => Some("UNSUPPORTED"),
+            NotificationCategory::Performance => Some("PERFORMANCE"),
+            NotificationCategory::Deprecation => Some("DEPRECATION"),
+            NotificationCategory::Security => Some("SECURITY"),
+            NotificationCategory::Topology => Some("TOPOLOGY"),
+            NotificationCategory::Generic => Some("GENERIC"),
+            NotificationCategory::Schema => Some("SCHEMA"),
+            NotificationCategory::Unknown => None,
+        }
+    }
+}
+
+/// Which notifications the server reports, configured for all connections with
+/// [`crate::ConfigBuilder::with_notification_filter`], or for some of the work with
+/// [`crate::SessionConfig::notification_filter`] and [`crate::TxConfig::notification_filter`].
+///
+/// Notifications that are filtered out are not even created by the server,
+/// which saves the work of building them for queries that run often.
+/// Filtering requires Bolt 5.2 or later, older servers reject any filter
+/// with [`Error::NotificationFilterNotSupported`].
+///
+/// ```
+/// use neo4rs::{NotificationCategory, NotificationFilter, NotificationSeverity};
+///
+/// let filter = NotificationFilter::new()
+///     .minimum_severity(NotificationSeverity::Warning)
+///     .disable_categories([NotificationCategory::Hint, NotificationCategory::Generic]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotificationFilter {
+    minimum_severity: Option<MinimumSeverity>,
+    disabled_categories: Option<Vec<NotificationCategory>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MinimumSeverity {
+    Off,
+    Warning,
+    Information,
+}
+
+impl NotificationFilter {
+    /// A filter that keeps the defaults of the server, or of the driver for sessions and transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A filter that disables all notifications.
+    pub fn disabled() -> Self {
+        Self {
+            minimum_severity: Some(MinimumSeverity::Off),
+            disabled_categories: None,
+        }
+    }
+
+    /// Only reports notifications that are at least as severe as the given severity.
+    /// [`NotificationSeverity::Unknown`] keeps the default.
+    pub fn minimum_severity(mut self, severity: NotificationSeverity) -> Self {
+        self.minimum_severity = match severity {
+            NotificationSeverity::Warning => Some(MinimumSeverity::Warning),
+            NotificationSeverity::Information => Some(MinimumSeverity::Information),
+            NotificationSeverity::Unknown => None,
+        };
+        self
+    }
+
+    /// Does not report notifications of the given categories.
+    /// An empty list enables all categories, overriding the filter of the driver.
+    pub fn disable_categories(
+        mut self,
+        categories: impl IntoIterator<Item = NotificationCategory>,
+    ) -> Self {
+        self.disabled_categories = Some(
+            categories
+                .into_iter()
+                .filter(|category| *category != NotificationCategory::Unknown)
+                .collect(),
+        );
+        self
+    }
+
+    /// Takes the settings that this filter leaves unset from the given filter.
+    pub(crate) fn or(self, defaults: &Self) -> Self {
+        Self {
+            minimum_severity: self.minimum_severity.or(defaults.minimum_severity),
+            disabled_categories: self
+                .disabled_categories
+                .or_else(|| defaults.disabled_categories.clone()),
+        }
+    }
+
+    /// Fails early if the server cannot filter notifications,
+    /// instead of silently reporting all of them.
+    pub(crate) fn check_version(&self, version: Version) -> Result<()> {
+        if *self != Self::default() && version < Version::V5_2 {
+            return Err(Error::NotificationFilterNotSupported(format!(
+                "filtering notifications requires Bolt 5.2 or later, but the server only supports {version:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The entries of the filter in HELLO, BEGIN and RUN.
+    /// Since Bolt 5.5, the categories are called classifications.
+    pub(crate) fn entries(&self, version: Version) -> Vec<(&'static str, BoltType)> {
+        let mut entries = Vec::new();
+        if let Some(severity) = self.minimum_severity {
+            let severity = match severity {
+                MinimumSeverity::Off => "OFF",
+                MinimumSeverity::Warning => "WARNING",
+                MinimumSeverity::Information => "INFORMATION",
+            };
+            entries.push(("notifications_minimum_severity", severity.into()));
+        }
+        if let Some(categories) = &self.disabled_categories {
+            let key = if version >= Version::V5_5 {
+                "notifications_disabled_classifications"
+            } else {
+                "notifications_disabled_categories"
+            };
+            let categories = categories
+                .iter()
+                .filter_map(|category| category.as_str())
+                .map(BoltType::from)
+                .collect::<Vec<_>>();
+            entries.push((key, BoltType::List(categories.into())));
+        }
+        entries
+    }
+}
+
+/// A position in the text of a query, see [`Notification::position`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputPosition {
+    offset: i64,
+    line: i64,
+    column: i64,
+}
+
+impl InputPosition {
+    fn parse(position: &BoltMap) -> Self {
+        Self {
+            offset: position.get::<i64>("offset").unwrap_or(0),
+            line: position.get::<i64>("line").unwrap_or(0),
+            column: position.get::<i64>("column").unwrap_or(0),
+        }
+    }
+
+    /// The number of characters before the position, starting at 0.
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// The line of the position, starting at 1.
+    pub fn line(&self) -> i64 {
+        self.line
+    }
+
+    /// The column of the position, starting at 1.
+    pub fn column(&self) -> i64 {
+        self.column
+    }
+}
+
+impl fmt::Display for InputPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// The outcome of a query as defined by the GQL standard, sent by servers with Bolt 5.5 or later,
+/// see [`ResultSummary::gql_status_objects`].
+///
+/// Besides the notifications, this includes e.g. `00000` for a successful completion
+/// or `02000` if the query did not return any data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GqlStatusObject {
+    gql_status: String,
+    status_description: String,
+    diagnostic_record: BoltMap,
+    is_notification: bool,
+}
+
+impl GqlStatusObject {
+    fn parse(status: &BoltMap) -> Option<Self> {
+        Some(Self {
+            gql_status: status.get::<String>("gql_status").ok()?,
+            status_description: status
+                .get::<String>("status_description")
+                .unwrap_or_default(),
+            diagnostic_record: status
+                .get::<BoltMap>("diagnostic_record")
+                .unwrap_or_default(),
+            is_notification: status.value.contains_key("neo4j_code"),
+        })
+    }
+
+    /// The GQLSTATUS code, e.g. `01N01` for a warning about deprecated syntax.
+    pub fn gql_status(&self) -> &str {
+        &self.gql_status
+    }
+
+    pub fn status_description(&self) -> &str {
+        &self.status_description
+    }
+
+    /// Further details about the status, e.g. `OPERATION`, `CURRENT_SCHEMA`,
+    /// or the Neo4j specific `_severity`, `_classification` and `_position`.
+    pub fn diagnostic_record(&self) -> &BoltMap {
+        &self.diagnostic_record
+    }
+
+    /// Returns `true` if the status is also reported as a [`Notification`].
+    pub fn is_notification(&self) -> bool {
+        self.is_notification
+    }
+
+    pub fn severity(&self) -> NotificationSeverity {
+        NotificationSeverity::parse(self.diagnostic_record.get::<&str>("_severity").ok())
+    }
+
+    pub fn classification(&self) -> NotificationCategory {
+        NotificationCategory::parse(self.diagnostic_record.get::<&str>("_classification").ok())
+    }
+
+    pub fn position(&self) -> Option<InputPosition> {
+        self.diagnostic_record
+            .get::<BoltMap>("_position")
+            .ok()
+            .map(|position| InputPosition::parse(&position))
+    }
+}
+
+/// Reads the GQL status objects and the notifications from the summary of a query.
+/// Since Bolt 5.5, the notifications are derived from the status objects.
+pub(crate) fn parse(
+    notifications: Option<Vec<BoltMap>>,
+    statuses: Option<Vec<BoltMap>>,
+) -> (Vec<Notification>, Vec<GqlStatusObject>) {
+    match statuses {
+        Some(statuses) => {
+            let mut notifications = Vec::new();
+            let mut objects = Vec::new();
+            for raw in statuses {
+                let Some(status) = GqlStatusObject::parse(&raw) else {
+                    continue;
+                };
+                notifications.extend(Notification::from_status(&status, &raw));
+                objects.push(status);
+            }
+            (notifications, objects)
+        }
+        None => {
+            let notifications = notifications
+                .unwrap_or_default()
+                .iter()
+                .filter_map(Notification::parse)
+                .collect();
+            (notifications, Vec::new())
+        }
+    }
+}
+
+type NotificationHandler = dyn Fn(&Notification) + Send + Sync;
+
+/// Logs the notifications of every query and passes them on to the handler
+/// of [`crate::ConfigBuilder::with_notification_handler`].
+#[derive(Clone, Default)]
+pub(crate) struct NotificationHook {
+    handler: Option<Arc<NotificationHandler>>,
+}
+
+impl NotificationHook {
+    pub(crate) fn new(handler: impl Fn(&Notification) + Send + Sync + 'static) -> Self {
+        Self {
+            handler: Some(Arc::new(handler)),
+        }
+    }
+
+    pub(crate) fn notify(&self, summary: &ResultSummary) {
+        for notification in summary.notifications() {
+            match notification.severity() {
+                NotificationSeverity::Warning => log::warn!("{notification}"),
+                _ => log::debug!("{notification}"),
+            }
+            if let Some(handler) = &self.handler {
+                handler(notification);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for NotificationHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotificationHook")
+            .field("handler", &self.handler.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoltType;
+
+    fn position() -> BoltType {
+        BoltType::Map(BoltMap::from_entries(vec![
+            ("offset", 9.into()),
+            ("line", 1.into()),
+            ("column", 10.into()),
+        ]))
+    }
+
+    #[test]
+    fn should_parse_notifications() {
+        let notification = BoltMap::from_entries(vec![
+            (
+                "code",
+                "Neo.ClientNotification.Statement.CartesianProduct".into(),
+            ),
+            ("title", "This query builds a cartesian product".into()),
+            ("description", "If a part of a query contains ...".into()),
+            ("severity", "INFORMATION".into()),
+            ("category", "PERFORMANCE".into()),
+            ("position", position()),
+        ]);
+        let (notifications, statuses) = parse(Some(vec![notification]), None);
+
+        assert!(statuses.is_empty());
+        let [notification] = &notifications[..] else {
+            panic!("expected one notification, got {notifications:?}");
+        };
+        assert_eq!(
+            notification.code(),
+            "Neo.ClientNotification.Statement.CartesianProduct"
+        );
+        assert_eq!(notification.severity(), NotificationSeverity::Information);
+        assert_eq!(notification.category(), NotificationCategory::Performance);
+        let position = notification.position().unwrap();
+        assert_eq!(
+            (position.offset(), position.line(), position.column()),
+            (9, 1, 10)
+        );
+        assert_eq!(
+            notification.to_string(),
+            "Neo.ClientNotification.Statement.CartesianProduct: \
+             This query builds a cartesian product (If a part of a query contains ...) \
+             at line 1, column 10"
+        );
+    }
+
+    #[test]
+    fn should_take_unset_settings_from_the_defaults() {
+        let defaults = NotificationFilter::new()
+            .minimum_severity(NotificationSeverity::Warning)
+            .disable_categories([NotificationCategory::Hint]);
+
+        let filter = NotificationFilter::new().disable_categories([]);
+        assert_eq!(
+            filter.or(&defaults),
+            NotificationFilter::new()
+                .minimum_severity(NotificationSeverity::Warning)
+                .disable_categories([])
+        );
+        assert_eq!(NotificationFilter::new().or(&defaults), defaults);
+        assert_eq!(
+            NotificationFilter::disabled().or(&defaults),
+            NotificationFilter::disabled().disable_categories([NotificationCategory::Hint])
+        );
+    }
+
+    #[test]
+    fn should_parse_gql_status_objects() {
+        let deprecation = BoltMap::from_entries(vec![
+            ("gql_status", "01N01".into()),
+            ("status_description", "warn: feature deprecated.".into()),
+            (
+                "neo4j_code",
+                "Neo.ClientNotification.Statement.FeatureDeprecationWarning".into(),
+            ),
+            ("title", "This feature is deprecated".into()),
+            (
+                "diagnostic_record",
+                BoltType::Map(BoltMap::from_entries(vec![
+                    ("OPERATION", "".into()),
+                    ("CURRENT_SCHEMA", "/".into()),
+                    ("_severity", "WARNING".into()),
+                    ("_classification", "DEPRECATION".into()),
+                    ("_position", position()),
+                ])),
+            ),
+        ]);
+        let no_data = BoltMap::from_entries(vec![
+            ("gql_status", "02000".into()),
+            ("status_description", "note: no data".into()),
+            ("diagnostic_record", BoltType::Map(BoltMap::default())),
+        ]);
+        let (notifications, statuses) = parse(None, Some(vec![deprecation, no_data]));
+
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].gql_status(), "01N01");
+        assert!(statuses[0].is_notification());
+        assert_eq!(statuses[0].severity(), NotificationSeverity::Warning);
+        assert_eq!(
+            statuses[0].classification(),
+            NotificationCategory::Deprecation
+        );
+        assert_eq!(
+            statuses[0]
+                .diagnostic_record()
+                .get::<String>("CURRENT_SCHEMA")
+                .unwrap(),
+            "/"
+        );
+        assert_eq!(statuses[1].gql_status(), "02000");
+        assert!(!statuses[1].is_notification());
+        assert_eq!(statuses[1].severity(), NotificationSeverity::Unknown);
+
+        let [notification] = &notifications[..] else {
+            panic!("expected one notification, got {notifications:?}");
+        };
+        assert_eq!(notification.title(), "This feature is deprecated");
+        assert_eq!(notification.description(), "warn: feature deprecated.");
+        assert_eq!(notification.category(), NotificationCategory::Deprecation);
+        assert_eq!(notification.position().unwrap().line(), 1);
+    }
+}
@@ -0,0 +1,381 @@
+use std::{fmt, time::Duration};
+
+use crate::{BoltList, BoltMap, BoltType};
+
+/// The execution plan of a query that ran with `EXPLAIN`, see [`crate::Query::explain`]
+/// and [`crate::ResultSummary::plan`].
+///
+/// The plan is a tree of operators, its root produces the result.
+/// Its [`fmt::Display`] implementation renders the plan as a table like `cypher-shell` does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    operator_type: String,
+    arguments: BoltMap,
+    identifiers: Vec<String>,
+    children: Vec<Plan>,
+}
+
+impl Plan {
+    pub(crate) fn parse(plan: &BoltMap) -> Option<Self> {
+        Some(Self {
+            operator_type: plan.get::<String>("operatorType").ok()?,
+            arguments: plan.get::<BoltMap>("args").unwrap_or_default(),
+            identifiers: plan.get::<Vec<String>>("identifiers").unwrap_or_default(),
+            children: children(plan, Plan::parse),
+        })
+    }
+
+    /// The name of the operator, e.g. `NodeByLabelScan`.
+    pub fn operator_type(&self) -> &str {
+        &self.operator_type
+    }
+
+    /// The arguments of the operator, e.g. `Details` or `EstimatedRows`.
+    pub fn arguments(&self) -> &BoltMap {
+        &self.arguments
+    }
+
+    /// The variables that the operator introduces or uses.
+    pub fn identifiers(&self) -> &[String] {
+        &self.identifiers
+    }
+
+    /// The operators that feed into this one.
+    pub fn children(&self) -> &[Plan] {
+        &self.children
+    }
+}
+
+/// The execution plan of a query that ran with `PROFILE`, see [`crate::Query::profile`]
+/// and [`crate::ResultSummary::profile`].
+///
+/// Like a [`Plan`], but every operator also reports how much work it has done.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfiledPlan {
+    operator_type: String,
+    arguments: BoltMap,
+    identifiers: Vec<String>,
+    children: Vec<ProfiledPlan>,
+    db_hits: u64,
+    rows: u64,
+    page_cache_hits: u64,
+    page_cache_misses: u64,
+    page_cache_hit_ratio: f64,
+    time: Duration,
+}
+
+impl ProfiledPlan {
+    pub(crate) fn parse(plan: &BoltMap) -> Option<Self> {
+        let count = |key: &str| plan.get::<u64>(key).unwrap_or(0);
+        Some(Self {
+            operator_type: plan.get::<String>("operatorType").ok()?,
+            arguments: plan.get::<BoltMap>("args").unwrap_or_default(),
+            identifiers: plan.get::<Vec<String>>("identifiers").unwrap_or_default(),
+            children: children(plan, ProfiledPlan::parse),
+            db_hits: count("dbHits"),
+            rows: count("rows"),
+            page_cache_hits: count("pageCacheHits"),
+            page_cache_misses: count("pageCacheMisses"),
+            page_cache_hit_ratio: plan.get::<f64>("pageCacheHitRatio").unwrap_or(0.0),
+            // the server reports the time in nanoseconds
+            time: Duration::from_nanos(count("time")),
+        })
+    }
+
+    /// The name of the operator, e.g. `NodeByLabelScan`.
+    pub fn operator_type(&self) -> &str {
+        &self.operator_type
+    }
+
+    /// The arguments of the operator, e.g. `Details` or `EstimatedRows`.
+    pub fn arguments(&self) -> &BoltMap {
+        &self.arguments
+    }
+
+    /// The variables that the operator introduces or uses.
+    pub fn identifiers(&self) -> &[String] {
+        &self.identifiers
+    }
+
+    /// The operators that feed into this one.
+    pub fn children(&self) -> &[ProfiledPlan] {
+        &self.children
+    }
+
+    /// How often the operator has accessed the storage.
+    pub fn db_hits(&self) -> u64 {
+        self.db_hits
+    }
+
+    /// The number of rows that the operator has produced.
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    pub fn page_cache_hits(&self) -> u64 {
+        self.page_cache_hits
+    }
+
+    pub fn page_cache_misses(&self) -> u64 {
+        self.page_cache_misses
+    }
+
+    pub fn page_cache_hit_ratio(&self) -> f64 {
+        self.page_cache_hit_ratio
+    }
+
+    /// The time that the operator has spent, if the runtime measures it.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+fn children<T>(plan: &BoltMap, parse: impl Fn(&BoltMap) -> Option<T>) -> Vec<T> {
+    let children = plan.get::<BoltList>("children").unwrap_or_default();
+    children
+        .value
+        .iter()
+        .filter_map(|child| match child {
+            BoltType::Map(child) => parse(child),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The columns of a row of the rendered table, see [`render`].
+trait Operator: Sized {
+    const HEADERS: &'static [&'static str];
+
+    fn operator_type(&self) -> &str;
+
+    fn children(&self) -> &[Self];
+
+    /// The cells after the one of the operator.
+    fn cells(&self) -> Vec<String>;
+}
+
+impl Operator for Plan {
+    const HEADERS: &'static [&'static str] = &["Operator", "Details", "Estimated Rows"];
+
+    fn operator_type(&self) -> &str {
+        &self.operator_type
+    }
+
+    fn children(&self) -> &[Self] {
+        &self.children
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![details(&self.arguments), estimated_rows(&self.arguments)]
+    }
+}
+
+impl Operator for ProfiledPlan {
+    const HEADERS: &'static [&'static str] = &[
+        "Operator",
+        "Details",
+        "Estimated Rows",
+        "Rows",
+        "DB Hits",
+        "Page Cache Hits/Misses",
+        "Time (ms)",
+    ];
+
+    fn operator_type(&self) -> &str {
+        &self.operator_type
+    }
+
+    fn children(&self) -> &[Self] {
+        &self.children
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            details(&self.arguments),
+            estimated_rows(&self.arguments),
+            self.rows.to_string(),
+            self.db_hits.to_string(),
+            format!("{}/{}", self.page_cache_hits, self.page_cache_misses),
+            format!("{:.3}", self.time.as_secs_f64() * 1000.0),
+        ]
+    }
+}
+
+fn details(arguments: &BoltMap) -> String {
+    arguments.get::<String>("Details").unwrap_or_default()
+}
+
+fn estimated_rows(arguments: &BoltMap) -> String {
+    arguments
+        .get::<f64>("EstimatedRows")
+        .map(|rows| format!("{}", rows.round()))
+        .unwrap_or_default()
+}
+
+/// Collects the rows of the operator and of all operators below it.
+/// Every child after the first one starts a new branch, which is indented one level deeper.
+fn collect_rows<T: Operator>(plan: &T, depth: usize, rows: &mut Vec<Vec<String>>) {
+    let operator = format!("{}+{}", "| ".repeat(depth), plan.operator_type());
+    rows.push([operator].into_iter().chain(plan.cells()).collect());
+    for (i, child) in plan.children().iter().enumerate() {
+        collect_rows(child, depth + usize::from(i > 0), rows);
+    }
+}
+
+/// Renders the plan as a table, with all columns but the first two aligned to the right.
+fn render<T: Operator>(plan: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut table = Vec::new();
+    collect_rows(plan, 0, &mut table);
+    let widths = T::HEADERS
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            table
+                .iter()
+                .map(|row| row[column].chars().count())
+                .chain([header.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let separator = widths
+        .iter()
+        .map(|width| "-".repeat(width + 2))
+        .collect::<Vec<_>>()
+        .join("+");
+    let separator = format!("+{separator}+");
+
+    writeln!(f, "{separator}")?;
+    for (header, width) in T::HEADERS.iter().zip(&widths) {
+        write!(f, "| {header:<width$} ")?;
+    }
+    writeln!(f, "|")?;
+    writeln!(f, "{separator}")?;
+    for row in &table {
+        for (column, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            if column < 2 {
+                write!(f, "| {cell:<width$} ")?;
+            } else {
+                write!(f, "| {cell:>width$} ")?;
+            }
+        }
+        writeln!(f, "|")?;
+    }
+    write!(f, "{separator}")
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self, f)
+    }
+}
+
+impl fmt::Display for ProfiledPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operator(name: &str, details: &str, children: Vec<BoltMap>) -> BoltMap {
+        BoltMap::from_entries(vec![
+            ("operatorType", name.into()),
+            (
+                "args",
+                BoltType::Map(BoltMap::from_entries(vec![
+                    ("Details", details.into()),
+                    ("EstimatedRows", 10.0.into()),
+                ])),
+            ),
+            (
+                "identifiers",
+                BoltType::List(vec![BoltType::from("n")].into()),
+            ),
+            (
+                "children",
+                BoltType::List(
+                    children
+                        .into_iter()
+                        .map(BoltType::Map)
+                        .collect::<Vec<_>>()
+                        .into(),
+                ),
+            ),
+            ("dbHits", 11.into()),
+            ("rows", 10.into()),
+            ("pageCacheHits", 3.into()),
+            ("pageCacheMisses", 1.into()),
+            ("pageCacheHitRatio", 0.75.into()),
+            ("time", 1_500_000.into()),
+        ])
+    }
+
+    fn union() -> BoltMap {
+        operator(
+            "ProduceResults@neo4j",
+            "n",
+            vec![operator(
+                "Union@neo4j",
+                "",
+                vec![
+                    operator("NodeByLabelScan@neo4j", "n:Person", vec![]),
+                    operator("NodeByLabelScan@neo4j", "n:Movie", vec![]),
+                ],
+            )],
+        )
+    }
+
+    #[test]
+    fn should_parse_plans() {
+        let plan = Plan::parse(&union()).unwrap();
+        assert_eq!(plan.operator_type(), "ProduceResults@neo4j");
+        assert_eq!(plan.identifiers(), ["n"]);
+        assert_eq!(plan.arguments().get::<String>("Details").unwrap(), "n");
+        let scans = plan.children()[0].children();
+        assert_eq!(scans.len(), 2);
+        assert_eq!(scans[1].operator_type(), "NodeByLabelScan@neo4j");
+
+        let profile = ProfiledPlan::parse(&union()).unwrap();
+        assert_eq!(profile.db_hits(), 11);
+        assert_eq!(profile.rows(), 10);
+        assert_eq!(profile.page_cache_hits(), 3);
+        assert_eq!(profile.page_cache_misses(), 1);
+        assert_eq!(profile.page_cache_hit_ratio(), 0.75);
+        assert_eq!(profile.time(), Duration::from_micros(1500));
+        assert_eq!(profile.children()[0].children().len(), 2);
+
+        assert_eq!(Plan::parse(&BoltMap::default()), None);
+    }
+
+    #[test]
+    fn should_render_plans_as_table() {
+        let plan = Plan::parse(&union()).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "\
++--------------------------+----------+----------------+
+| Operator                 | Details  | Estimated Rows |
++--------------------------+----------+----------------+
+| +ProduceResults@neo4j    | n        |             10 |
+| +Union@neo4j             |          |             10 |
+| +NodeByLabelScan@neo4j   | n:Person |             10 |
+| | +NodeByLabelScan@neo4j | n:Movie  |             10 |
++--------------------------+----------+----------------+"
+        );
+
+        let profile = ProfiledPlan::parse(&operator("AllNodesScan", "n", vec![])).unwrap();
+        assert_eq!(
+            profile.to_string(),
+            "\
++---------------+---------+----------------+------+---------+------------------------+-----------+
+| Operator      | Details | Estimated Rows | Rows | DB Hits | Page Cache Hits/Misses | Time (ms) |
++---------------+---------+----------------+------+---------+------------------------+-----------+
+| +AllNodesScan | n       |             10 |   10 |      11 |                    3/1 |     1.500 |
++---------------+---------+----------------+------+---------+------------------------+-----------+"
+        );
+    }
+}
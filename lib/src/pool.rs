@@ -1,21 +1,38 @@
-use std::time::Duration;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    auth::ClientCertificate,
-    config::Config,
+    auth::{AuthToken, ClientCertificate, Credentials, StaticAuthManager},
+    config::{AccessMode, Config, Database, PoolOptions, Timeouts},
     connection::{Connection, ConnectionInfo},
     errors::{Error, Result},
+    metrics::{PoolCounters, PoolMetrics, RetryCounter},
+    routing::RoutedConnectionManager,
+    telemetry,
+    tls::TlsConfig,
 };
 use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
-use deadpool::managed::{Manager, Metrics, Object, Pool, RecycleResult};
-use log::info;
+use deadpool::{
+    managed::{Manager, Metrics, Object, Pool, RecycleError, RecycleResult, WeakPool},
+    Runtime,
+};
+use log::{debug, info};
+use tokio::time::MissedTickBehavior;
+
+/// Idle connections are not checked for eviction more often than every half of this.
+const MIN_EVICTION_INTERVAL: Duration = Duration::from_secs(1);
 
 pub type ConnectionPool = Pool<ConnectionManager>;
 pub type ManagedConnection = Object<ConnectionManager>;
 
 pub struct ConnectionManager {
     info: ConnectionInfo,
+    options: PoolOptions,
     backoff: ExponentialBackoff,
+    counters: PoolCounters,
+    retries: RetryCounter,
 }
 
 impl ConnectionManager {
@@ -25,14 +42,23 @@ impl ConnectionManager {
         password: &str,
         client_certificate: Option<&ClientCertificate>,
     ) -> Result<Self> {
-        let info = ConnectionInfo::new(uri, user, password, client_certificate)?;
-        let backoff = ExponentialBackoffBuilder::new()
-            .with_initial_interval(Duration::from_millis(1))
-            .with_randomization_factor(0.42)
-            .with_multiplier(2.0)
-            .with_max_elapsed_time(Some(Duration::from_secs(60)))
-            .build();
-        Ok(ConnectionManager { info, backoff })
+        let auth = Arc::new(StaticAuthManager::new(AuthToken::basic(user, password)));
+        let tls = match client_certificate {
+            Some(certificate) => TlsConfig::new().with_ca_certificates_file(&certificate.cert_file),
+            None => TlsConfig::new(),
+        };
+        let info = ConnectionInfo::new(uri, auth, &tls, Timeouts::default())?;
+        Ok(Self::with_info(info, PoolOptions::default()))
+    }
+
+    pub(crate) fn with_info(info: ConnectionInfo, options: PoolOptions) -> Self {
+        ConnectionManager {
+            counters: PoolCounters::new(info.server().to_string()),
+            retries: RetryCounter::default(),
+            info,
+            options,
+            backoff: default_backoff(),
+        }
     }
 
     pub fn backoff(&self) -> ExponentialBackoff {
@@ -40,33 +66,527 @@ impl ConnectionManager {
     }
 }
 
+pub(crate) fn default_backoff() -> ExponentialBackoff {
+    ExponentialBackoffBuilder::new()
+        .with_initial_interval(Duration::from_millis(1))
+        .with_randomization_factor(0.42)
+        .with_multiplier(2.0)
+        .with_max_elapsed_time(Some(Duration::from_secs(60)))
+        .build()
+}
+
 impl Manager for ConnectionManager {
     type Type = Connection;
     type Error = Error;
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
         info!("creating new connection...");
-        Connection::new(&self.info).await
+        // opening a connection is a large future, which is kept off the stack of the caller
+        let connection = Box::pin(Connection::new(&self.info)).await;
+        match connection {
+            Ok(_) => self.counters.connection_created(),
+            Err(_) => self.counters.connection_failed(),
+        }
+        connection
+    }
+
+    fn detach(&self, _obj: &mut Self::Type) {
+        self.counters.connection_closed();
+    }
+
+    async fn recycle(&self, obj: &mut Self::Type, metrics: &Metrics) -> RecycleResult<Self::Error> {
+        if obj.is_defunct() {
+            return Err(RecycleError::message("the connection is defunct"));
+        }
+        if let Some(max_lifetime) = self.options.max_lifetime {
+            if metrics.age() >= max_lifetime {
+                return Err(RecycleError::message(
+                    "the connection has exceeded its max lifetime",
+                ));
+            }
+        }
+        // the RESET doubles as the liveness check
+        let check_liveness = self
+            .options
+            .liveness_check
+            .is_some_and(|idle_time| obj.idle_time() >= idle_time);
+        if check_liveness || !obj.is_ready() {
+            obj.reset().await?;
+        }
+        // connections that can re-authenticate switch their credentials in `acquire`,
+        // which knows whether they are used by a session with its own credentials
+        if !obj.can_reauthenticate()
+            && !obj.is_authenticated_with(&self.info.auth().current().await?)
+        {
+            return Err(RecycleError::message(
+                "the credentials have changed and the connection cannot re-authenticate",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Hands out connections either from a single pool for a direct connection (`bolt://`)
+/// or from the pool of a server selected from the routing table (`neo4j://`).
+#[derive(Clone)]
+pub(crate) enum ConnectionPoolManager {
+    Direct(ConnectionPool),
+    Routed(Arc<RoutedConnectionManager>),
+}
+
+impl ConnectionPoolManager {
+    /// Acquires a connection that is authenticated with the given token of a session,
+    /// or with the credentials of the pool if there is none.
+    /// The bookmarks are sent along if the routing table has to be fetched.
+    pub(crate) async fn get(
+        &self,
+        db: Option<&str>,
+        mode: AccessMode,
+        auth: Option<&AuthToken>,
+        bookmarks: &[String],
+    ) -> Result<ManagedConnection> {
+        match self {
+            ConnectionPoolManager::Direct(pool) => acquire(pool, auth).await,
+            ConnectionPoolManager::Routed(manager) => manager.get(db, mode, auth, bookmarks).await,
+        }
+    }
+
+    pub(crate) fn backoff(&self) -> ExponentialBackoff {
+        match self {
+            ConnectionPoolManager::Direct(pool) => pool.manager().backoff(),
+            ConnectionPoolManager::Routed(manager) => manager.backoff(),
+        }
+    }
+
+    /// Resolves the name of the home database of the user ahead of time, if possible.
+    /// Direct connections resolve it from the summary of the first query instead.
+    pub(crate) async fn resolve_home_db(
+        &self,
+        imp_user: Option<&str>,
+        bookmarks: &[String],
+    ) -> Result<Option<Database>> {
+        match self {
+            ConnectionPoolManager::Direct(_) => Ok(None),
+            ConnectionPoolManager::Routed(manager) => {
+                manager.resolve_home_db(imp_user, bookmarks).await
+            }
+        }
+    }
+
+    /// The credentials that the connections of the pool authenticate with right now.
+    pub(crate) async fn credentials(&self) -> Result<Credentials> {
+        self.info().auth().current().await
+    }
+
+    /// Gives the routing table a chance to react to errors that happened on one of its connections.
+    pub(crate) fn on_error(&self, db: Option<&str>, error: &backoff::Error<Error>) {
+        if let ConnectionPoolManager::Routed(manager) = self {
+            let (backoff::Error::Permanent(error) | backoff::Error::Transient { err: error, .. }) =
+                error;
+            manager.on_error(db, error);
+        }
+    }
+
+    /// Passes security errors on to the auth manager. If it provides new credentials,
+    /// the error is returned as transient so that the work is retried with them.
+    pub(crate) fn on_security_error(&self, error: backoff::Error<Error>) -> backoff::Error<Error> {
+        let reauthenticate = match &error {
+            backoff::Error::Permanent(Error::Neo4j(e))
+            | backoff::Error::Transient {
+                err: Error::Neo4j(e),
+                ..
+            } => self.info().auth().on_error(e),
+            _ => false,
+        };
+        match error {
+            backoff::Error::Permanent(e) if reauthenticate => backoff::Error::transient(e),
+            error => error,
+        }
+    }
+
+    /// Counts a retry of a query or transaction.
+    pub(crate) fn retried(&self) {
+        match self {
+            ConnectionPoolManager::Direct(pool) => pool.manager().retries.retried(),
+            ConnectionPoolManager::Routed(manager) => manager.retries().retried(),
+        }
+    }
+
+    pub(crate) fn retries(&self) -> u64 {
+        match self {
+            ConnectionPoolManager::Direct(pool) => pool.manager().retries.get(),
+            ConnectionPoolManager::Routed(manager) => manager.retries().get(),
+        }
+    }
+
+    /// The metrics of every pool, one for a direct connection and one per server when routing.
+    pub(crate) fn pool_metrics(&self) -> Vec<PoolMetrics> {
+        match self {
+            ConnectionPoolManager::Direct(pool) => vec![metrics(pool)],
+            ConnectionPoolManager::Routed(manager) => manager.pool_metrics(),
+        }
+    }
+
+    fn info(&self) -> &ConnectionInfo {
+        match self {
+            ConnectionPoolManager::Direct(pool) => &pool.manager().info,
+            ConnectionPoolManager::Routed(manager) => manager.info(),
+        }
+    }
+}
+
+pub(crate) fn build_pool(info: ConnectionInfo, options: PoolOptions) -> ConnectionPool {
+    let acquire_timeout = info.timeouts().acquire;
+    let pool = ConnectionPool::builder(ConnectionManager::with_info(info, options))
+        .max_size(options.max_connections)
+        .wait_timeout(acquire_timeout)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .expect("The runtime is configured for the timeouts");
+    if let Some(idle_timeout) = options.idle_timeout {
+        tokio::spawn(evict_idle_connections(
+            pool.weak(),
+            idle_timeout,
+            options.min_connections,
+        ));
+    }
+    pool
+}
+
+/// Acquires a connection from the pool and records how long that took.
+/// Acquires a connection from the pool and authenticates it with the given token of a session,
+/// or with the credentials of the pool if there is none.
+pub(crate) async fn acquire(
+    pool: &ConnectionPool,
+    auth: Option<&AuthToken>,
+) -> Result<ManagedConnection> {
+    let span = telemetry::acquire(&pool.manager().info.server());
+    let start = Instant::now();
+    let connection = telemetry::instrument(span.clone(), pool.get())
+        .await
+        .map_err(Error::from);
+    telemetry::finished(&span, &connection);
+    let mut connection = connection?;
+    pool.manager().counters.connection_acquired(start.elapsed());
+    authenticate(&mut connection, &pool.manager().info, auth).await?;
+    Ok(connection)
+}
+
+/// Switches the credentials of the connection with LOGOFF and LOGON,
+/// unless it is authenticated with them already, e.g. from the last session of the same user.
+async fn authenticate(
+    connection: &mut Connection,
+    info: &ConnectionInfo,
+    auth: Option<&AuthToken>,
+) -> Result<()> {
+    let credentials = match auth {
+        Some(token) => Credentials::session(token.clone()),
+        None => info.auth().current().await?,
+    };
+    if connection.is_authenticated_with(&credentials) {
+        return Ok(());
+    }
+    if !connection.can_reauthenticate() {
+        return match auth {
+            Some(_) => Err(Error::SessionAuthNotSupported(format!(
+                "switching users requires Bolt 5.1 or later, but the server only supports {:?}",
+                connection.version()
+            ))),
+            // recycling replaces such connections, so this one has just been authenticated
+            None => Ok(()),
+        };
     }
+    connection.reauthenticate(credentials).await
+}
+
+pub(crate) fn metrics(pool: &ConnectionPool) -> PoolMetrics {
+    pool.manager().counters.snapshot(pool.status())
+}
+
+/// Periodically closes the connections that have been idle for longer than the timeout,
+/// until the pool is closed or dropped.
+async fn evict_idle_connections(
+    pool: WeakPool<ConnectionManager>,
+    idle_timeout: Duration,
+    min: usize,
+) {
+    let mut interval = tokio::time::interval(idle_timeout.max(MIN_EVICTION_INTERVAL) / 2);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(pool) = pool.upgrade() else {
+            return;
+        };
+        if pool.is_closed() {
+            return;
+        }
+        evict_idle(&pool, idle_timeout, min);
+    }
+}
 
-    async fn recycle(&self, obj: &mut Self::Type, _: &Metrics) -> RecycleResult<Self::Error> {
-        Ok(obj.reset().await?)
+/// Closes the connections that have been idle for longer than the timeout,
+/// but keeps at least `min` connections open.
+fn evict_idle(pool: &ConnectionPool, idle_timeout: Duration, min: usize) -> usize {
+    let mut evictable = pool.status().size.saturating_sub(min);
+    let result = pool.retain(|connection, _| {
+        let evict = evictable > 0 && connection.idle_time() >= idle_timeout;
+        if evict {
+            evictable -= 1;
+        }
+        !evict
+    });
+    if !result.removed.is_empty() {
+        debug!("closed {} idle connections", result.removed.len());
     }
+    result.removed.len()
 }
 
-pub async fn create_pool(config: &Config) -> Result<ConnectionPool> {
-    let mgr = ConnectionManager::new(
+/// Opens connections until the pool holds at least `min` of them.
+pub(crate) async fn warm_up(pool: &ConnectionPool, min: usize) -> Result<()> {
+    let missing = min.saturating_sub(pool.status().size);
+    let connections = futures::future::try_join_all((0..missing).map(|_| pool.get())).await?;
+    debug!(
+        "opened {} connections to warm up the pool",
+        connections.len()
+    );
+    Ok(())
+}
+
+pub(crate) async fn create_pool(config: &Config) -> Result<ConnectionPoolManager> {
+    let info = ConnectionInfo::new(
         &config.uri,
-        &config.user,
-        &config.password,
-        config.client_certificate.as_ref(),
+        config.auth.clone(),
+        &config.tls,
+        config.timeouts,
     )?;
+    let info = info
+        .with_notifications(config.notifications.clone())
+        .with_notification_filter(config.notification_filter.clone());
+    #[cfg(feature = "tracing")]
+    let info = info.trace_query_text(config.trace_query_text);
     info!(
         "creating connection pool with max size {}",
-        config.max_connections
+        config.pool.max_connections
     );
-    Ok(ConnectionPool::builder(mgr)
-        .max_size(config.max_connections)
-        .build()
-        .expect("No timeouts configured"))
+    let pool = if info.routing_context().is_some() {
+        let manager = RoutedConnectionManager::new(info, config.pool);
+        ConnectionPoolManager::Routed(Arc::new(manager))
+    } else {
+        ConnectionPoolManager::Direct(build_pool(info, config.pool))
+    };
+    if config.pool.warm_up {
+        match &pool {
+            ConnectionPoolManager::Direct(pool) => {
+                warm_up(pool, config.pool.min_connections).await?
+            }
+            ConnectionPoolManager::Routed(manager) => manager.warm_up().await?,
+        }
+    }
+    Ok(pool)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::{
+        connection::tests::{hello_with_version, no_auth},
+        messages::BoltRequest,
+        Version,
+    };
+
+    #[derive(Default)]
+    struct Counts {
+        connections: AtomicUsize,
+        messages: AtomicUsize,
+        signatures: Mutex<Vec<u8>>,
+    }
+
+    /// Starts a server that accepts any number of connections
+    /// and answers every message after the HELLO with an empty SUCCESS.
+    async fn server(options: PoolOptions) -> (ConnectionPool, Arc<Counts>) {
+        server_with_version(options, [0, 0, 4, 4]).await
+    }
+
+    async fn server_with_version(
+        options: PoolOptions,
+        version: [u8; 4],
+    ) -> (ConnectionPool, Arc<Counts>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let counts = Arc::new(Counts::default());
+        tokio::spawn({
+            let counts = counts.clone();
+            async move {
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    counts.connections.fetch_add(1, Ordering::SeqCst);
+                    let counts = counts.clone();
+                    tokio::spawn(async move {
+                        let mut stream = hello_with_version(stream, version, &[0xA0]).await;
+                        let mut first_chunk = true;
+                        while let Ok(chunk_size) = stream.read_u16().await {
+                            if chunk_size == 0 {
+                                counts.messages.fetch_add(1, Ordering::SeqCst);
+                                first_chunk = true;
+                                stream
+                                    .write_all(&[0x00, 0x03, 0xB1, 0x70, 0xA0, 0x00, 0x00])
+                                    .await
+                                    .unwrap();
+                            } else {
+                                let mut chunk = vec![0; usize::from(chunk_size)];
+                                stream.read_exact(&mut chunk).await.unwrap();
+                                if first_chunk {
+                                    counts.signatures.lock().unwrap().push(chunk[1]);
+                                    first_chunk = false;
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+        });
+
+        let info = ConnectionInfo::new(
+            &format!("bolt://{address}"),
+            no_auth(),
+            &TlsConfig::default(),
+            Timeouts::default(),
+        )
+        .unwrap();
+        (build_pool(info, options), counts)
+    }
+
+    #[tokio::test]
+    async fn should_warm_up_and_evict_idle_connections() {
+        let options = PoolOptions {
+            min_connections: 3,
+            ..PoolOptions::default()
+        };
+        let (pool, counts) = server(options).await;
+
+        warm_up(&pool, 3).await.unwrap();
+        assert_eq!(pool.status().size, 3);
+        assert_eq!(counts.connections.load(Ordering::SeqCst), 3);
+
+        let in_use = acquire(&pool, None).await.unwrap();
+        assert_eq!(evict_idle(&pool, Duration::from_secs(60), 1), 0);
+        assert_eq!(evict_idle(&pool, Duration::ZERO, 1), 2);
+        assert_eq!(pool.status().size, 1);
+
+        let pool_metrics = metrics(&pool);
+        assert_eq!(
+            pool_metrics.address(),
+            pool.manager().info.server().to_string()
+        );
+        assert_eq!(pool_metrics.in_use(), 1);
+        assert_eq!(pool_metrics.idle(), 0);
+        assert_eq!(pool_metrics.created(), 3);
+        assert_eq!(pool_metrics.closed(), 2);
+        assert_eq!(pool_metrics.failed(), 0);
+        assert_eq!(pool_metrics.acquisition_time().count(), 1);
+
+        drop(in_use);
+        assert_eq!(evict_idle(&pool, Duration::ZERO, 1), 0);
+        assert_eq!(metrics(&pool).idle(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_only_check_the_liveness_of_idle_connections() {
+        let options = PoolOptions {
+            liveness_check: Some(Duration::from_secs(60)),
+            ..PoolOptions::default()
+        };
+        let (pool, counts) = server(options).await;
+
+        drop(pool.get().await.unwrap());
+        drop(pool.get().await.unwrap());
+        assert_eq!(counts.messages.load(Ordering::SeqCst), 0);
+
+        let options = PoolOptions {
+            liveness_check: Some(Duration::ZERO),
+            ..PoolOptions::default()
+        };
+        let (pool, counts) = server(options).await;
+
+        drop(pool.get().await.unwrap());
+        drop(pool.get().await.unwrap());
+        assert_eq!(counts.messages.load(Ordering::SeqCst), 1);
+        assert_eq!(counts.connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn should_reset_connections_that_are_not_ready() {
+        let options = PoolOptions {
+            liveness_check: None,
+            ..PoolOptions::default()
+        };
+        let (pool, counts) = server(options).await;
+
+        let mut connection = pool.get().await.unwrap();
+        connection
+            .send(BoltRequest::begin(
+                None,
+                &[],
+                &Default::default(),
+                Version::V4_4,
+            ))
+            .await
+            .unwrap();
+        connection.recv().await.unwrap();
+        drop(connection);
+
+        let connection = pool.get().await.unwrap();
+        assert!(connection.is_ready());
+        assert_eq!(counts.messages.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn should_replace_connections_after_their_max_lifetime() {
+        let options = PoolOptions {
+            max_lifetime: Some(Duration::ZERO),
+            ..PoolOptions::default()
+        };
+        let (pool, counts) = server(options).await;
+
+        drop(pool.get().await.unwrap());
+        drop(pool.get().await.unwrap());
+        assert_eq!(counts.connections.load(Ordering::SeqCst), 2);
+        assert_eq!(counts.messages.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn should_only_switch_credentials_when_they_change() {
+        const LOGON: u8 = 0x6A;
+        const LOGOFF: u8 = 0x6B;
+        let options = PoolOptions {
+            liveness_check: None,
+            ..PoolOptions::default()
+        };
+        // Bolt 5.1
+        let (pool, counts) = server_with_version(options, [0, 0, 1, 5]).await;
+        let jane = AuthToken::basic("jane", "secret");
+
+        drop(acquire(&pool, Some(&jane)).await.unwrap());
+        assert_eq!(*counts.signatures.lock().unwrap(), [LOGON, LOGOFF, LOGON]);
+
+        drop(acquire(&pool, Some(&jane)).await.unwrap());
+        assert_eq!(counts.messages.load(Ordering::SeqCst), 3);
+
+        drop(acquire(&pool, None).await.unwrap());
+        assert_eq!(
+            *counts.signatures.lock().unwrap(),
+            [LOGON, LOGOFF, LOGON, LOGOFF, LOGON]
+        );
+        assert_eq!(counts.connections.load(Ordering::SeqCst), 1);
+    }
 }
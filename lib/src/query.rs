@@ -1,7 +1,11 @@
 use crate::{
+    bookmarks::Bookmarks,
+    config::TxConfig,
     errors::Result,
+    home_db::HomeDatabase,
     messages::{BoltRequest, BoltResponse},
     pool::ManagedConnection,
+    result_summary::ResultSummary,
     stream::{DetachedRowStream, RowStream},
     types::{BoltList, BoltMap, BoltString, BoltType},
     Error, Success,
@@ -39,43 +43,104 @@ impl Query {
         self
     }
 
+    /// Prefixes the query with `EXPLAIN`, so that the server only plans it without running it.
+    /// The plan is available from [`crate::ResultSummary::plan`], e.g. of [`crate::Graph::run`].
+    pub fn explain(self) -> Self {
+        Query {
+            query: format!("EXPLAIN {}", self.query),
+            ..self
+        }
+    }
+
+    /// Prefixes the query with `PROFILE`, so that the server tracks the work of every operator.
+    /// The profiled plan is available from [`crate::ResultSummary::profile`],
+    /// e.g. of [`crate::Graph::run`] or once all rows of [`crate::Graph::execute`] were consumed.
+    pub fn profile(self) -> Self {
+        Query {
+            query: format!("PROFILE {}", self.query),
+            ..self
+        }
+    }
+
     pub fn has_param_key(&self, key: &str) -> bool {
         self.params.value.contains_key(key)
     }
 
-    pub(crate) async fn run(self, db: &str, connection: &mut ManagedConnection) -> Result<()> {
+    pub(crate) async fn run(
+        self,
+        db: Option<&str>,
+        connection: &mut ManagedConnection,
+    ) -> Result<ResultSummary> {
         let request = BoltRequest::run(db, &self.query, self.params);
         Self::try_run(request, connection)
             .await
+            .map(|(summary, _)| summary)
             .map_err(unwrap_backoff)
     }
 
     pub(crate) async fn run_retryable(
         &self,
-        db: &str,
+        db: Option<&str>,
+        bookmarks: &Bookmarks,
+        home_db: Option<&HomeDatabase>,
+        config: &TxConfig,
         connection: &mut ManagedConnection,
-    ) -> Result<(), backoff::Error<Error>> {
-        let request = BoltRequest::run(db, &self.query, self.params.clone());
-        Self::try_run(request, connection).await
+    ) -> Result<ResultSummary, backoff::Error<Error>> {
+        config
+            .check_version(connection.version())
+            .map_err(backoff::Error::permanent)?;
+        let request = BoltRequest::run_auto_commit(
+            db,
+            &self.query,
+            self.params.clone(),
+            bookmarks.used(),
+            config,
+            connection.version(),
+        );
+        let (summary, success) = Self::try_run(request, connection)
+            .await
+            .map_err(|e| for_impersonation(e, config))?;
+        bookmarks.update(&success);
+        if let Some(home_db) = home_db {
+            home_db.update(&success);
+        }
+        Ok(summary)
     }
 
     pub(crate) async fn execute_retryable(
         &self,
-        db: &str,
+        db: Option<&str>,
         fetch_size: usize,
+        bookmarks: Bookmarks,
+        home_db: Option<HomeDatabase>,
+        config: &TxConfig,
         mut connection: ManagedConnection,
     ) -> Result<DetachedRowStream, backoff::Error<Error>> {
-        let request = BoltRequest::run(db, &self.query, self.params.clone());
+        config
+            .check_version(connection.version())
+            .map_err(backoff::Error::permanent)?;
+        let request = BoltRequest::run_auto_commit(
+            db,
+            &self.query,
+            self.params.clone(),
+            bookmarks.used(),
+            config,
+            connection.version(),
+        );
         Self::try_execute(request, fetch_size, &mut connection)
             .await
-            .map(|stream| DetachedRowStream::new(stream, connection))
+            .map_err(|e| for_impersonation(e, config))
+            .map(|stream| {
+                let stream = stream.with_bookmarks(bookmarks).with_home_database(home_db);
+                DetachedRowStream::new(stream, connection)
+            })
     }
 
-    pub(crate) async fn execute_mut<'conn>(
+    pub(crate) async fn execute_mut(
         self,
-        db: &str,
+        db: Option<&str>,
         fetch_size: usize,
-        connection: &'conn mut ManagedConnection,
+        connection: &mut ManagedConnection,
     ) -> Result<RowStream> {
         let run = BoltRequest::run(db, &self.query, self.params);
         Self::try_execute(run, fetch_size, connection)
@@ -83,10 +148,17 @@ impl Query {
             .map_err(unwrap_backoff)
     }
 
-    async fn try_run(request: BoltRequest, connection: &mut ManagedConnection) -> QueryResult<()> {
-        let _ = Self::try_request(request, connection).await?;
-        match connection.send_recv(BoltRequest::discard()).await {
-            Ok(BoltResponse::Success(_)) => Ok(()),
+    async fn try_run(
+        request: BoltRequest,
+        connection: &mut ManagedConnection,
+    ) -> QueryResult<(ResultSummary, Success)> {
+        let run = Self::try_request(request, connection).await?;
+        match connection.send_recv(BoltRequest::discard(-1)).await {
+            Ok(BoltResponse::Success(success)) => {
+                let t_first = run.get("t_first").ok();
+                let summary = connection.summary(t_first, &success);
+                Ok((summary, success))
+            }
             otherwise => wrap_error(otherwise, "DISCARD"),
         }
     }
@@ -96,11 +168,11 @@ impl Query {
         fetch_size: usize,
         connection: &mut ManagedConnection,
     ) -> QueryResult<RowStream> {
-        Self::try_request(request, connection).await.map(|success| {
-            let fields: BoltList = success.get("fields").unwrap_or_default();
-            let qid: i64 = success.get("qid").unwrap_or(-1);
-            RowStream::new(qid, fields, fetch_size)
-        })
+        let success = Self::try_request(request, connection).await?;
+        let fields: BoltList = success.get("fields").unwrap_or_default();
+        let qid: i64 = success.get("qid").unwrap_or(-1);
+        let t_first = success.get("t_first").ok();
+        Ok(RowStream::new(qid, fields, fetch_size, t_first))
     }
 
     async fn try_request(
@@ -134,15 +206,29 @@ fn wrap_error<T>(resp: Result<BoltResponse>, req: &'static str) -> QueryResult<T
         Ok(resp) => resp.into_error(req),
         Err(e) => e,
     };
+    Err(retryable(error))
+}
+
+/// Marks errors that are worth retrying on a fresh connection as transient.
+pub(crate) fn retryable(error: Error) -> backoff::Error<Error> {
     let can_retry = match &error {
         Error::Neo4j(e) => e.can_retry(),
         _ => false,
     };
 
     if can_retry {
-        Err(backoff::Error::transient(error))
+        backoff::Error::transient(error)
     } else {
-        Err(backoff::Error::permanent(error))
+        backoff::Error::permanent(error)
+    }
+}
+
+fn for_impersonation(err: backoff::Error<Error>, config: &TxConfig) -> backoff::Error<Error> {
+    match err {
+        backoff::Error::Permanent(e) => {
+            backoff::Error::permanent(e.for_impersonation(config.imp_user.as_deref()))
+        }
+        err => err,
     }
 }
 
@@ -0,0 +1,321 @@
+use std::time::Duration;
+
+use crate::{
+    messages::Success,
+    notification::{self, GqlStatusObject, Notification},
+    plan::{Plan, ProfiledPlan},
+    routing::BoltServer,
+    BoltMap,
+};
+
+/// What the server reports once a query has completed,
+/// returned by [`crate::Graph::run`], [`crate::Txn::run`]
+/// and by [`crate::RowStream::summary`] once all rows have been consumed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultSummary {
+    counters: Counters,
+    query_type: Option<QueryType>,
+    database: Option<String>,
+    server_address: String,
+    result_available_after: Option<Duration>,
+    result_consumed_after: Option<Duration>,
+    plan: Option<Plan>,
+    profile: Option<ProfiledPlan>,
+    notifications: Vec<Notification>,
+    gql_status_objects: Vec<GqlStatusObject>,
+}
+
+impl ResultSummary {
+    /// Combines the `t_first` of the success of RUN
+    /// with the success of the PULL or DISCARD that completed the result.
+    pub(crate) fn new(server: &BoltServer, t_first: Option<i64>, summary: &Success) -> Self {
+        let counters = summary
+            .get::<BoltMap>("stats")
+            .map(|stats| Counters::new(&stats))
+            .unwrap_or_default();
+        let (notifications, gql_status_objects) = notification::parse(
+            summary.get::<Vec<BoltMap>>("notifications").ok(),
+            summary.get::<Vec<BoltMap>>("statuses").ok(),
+        );
+        Self {
+            counters,
+            query_type: summary.get::<&str>("type").ok().and_then(QueryType::parse),
+            database: summary.get::<String>("db").ok(),
+            server_address: server.to_string(),
+            result_available_after: t_first.and_then(millis),
+            result_consumed_after: summary.get::<i64>("t_last").ok().and_then(millis),
+            plan: summary
+                .get::<BoltMap>("plan")
+                .ok()
+                .and_then(|plan| Plan::parse(&plan)),
+            profile: summary
+                .get::<BoltMap>("profile")
+                .ok()
+                .and_then(|profile| ProfiledPlan::parse(&profile)),
+            notifications,
+            gql_status_objects,
+        }
+    }
+
+    /// What the query has changed.
+    pub fn counters(&self) -> &Counters {
+        &self.counters
+    }
+
+    /// Whether the query has read or written, if the server reported it.
+    pub fn query_type(&self) -> Option<QueryType> {
+        self.query_type
+    }
+
+    /// The database the query ran on, if the server reported it (Bolt 4.0 and later).
+    pub fn database(&self) -> Option<&str> {
+        self.database.as_deref()
+    }
+
+    /// The address of the server the query ran on, e.g. `localhost:7687`.
+    pub fn server_address(&self) -> &str {
+        &self.server_address
+    }
+
+    /// How long the server took until the first row was available (`t_first`).
+    pub fn result_available_after(&self) -> Option<Duration> {
+        self.result_available_after
+    }
+
+    /// How long the server took to stream the rows after the first one was available (`t_last`).
+    pub fn result_consumed_after(&self) -> Option<Duration> {
+        self.result_consumed_after
+    }
+
+    /// The execution plan of a query that ran with `EXPLAIN`, see [`crate::Query::explain`].
+    pub fn plan(&self) -> Option<&Plan> {
+        self.plan.as_ref()
+    }
+
+    /// The profiled execution plan of a query that ran with `PROFILE`,
+    /// see [`crate::Query::profile`].
+    pub fn profile(&self) -> Option<&ProfiledPlan> {
+        self.profile.as_ref()
+    }
+
+    /// The warnings and hints of the server about the query,
+    /// which are also logged and passed to [`crate::ConfigBuilder::with_notification_handler`].
+    pub fn notifications(&self) -> &[Notification] {
+        &self.notifications
+    }
+
+    /// The GQL status objects of the query, which are only sent by servers with Bolt 5.5 or later.
+    pub fn gql_status_objects(&self) -> &[GqlStatusObject] {
+        &self.gql_status_objects
+    }
+}
+
+/// The changes that a query has made, see [`ResultSummary::counters`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counters {
+    nodes_created: u64,
+    nodes_deleted: u64,
+    relationships_created: u64,
+    relationships_deleted: u64,
+    properties_set: u64,
+    labels_added: u64,
+    labels_removed: u64,
+    indexes_added: u64,
+    indexes_removed: u64,
+    constraints_added: u64,
+    constraints_removed: u64,
+    system_updates: u64,
+    contains_updates: bool,
+    contains_system_updates: bool,
+}
+
+impl Counters {
+    fn new(stats: &BoltMap) -> Self {
+        let count = |key: &str| stats.get::<u64>(key).unwrap_or(0);
+        let mut counters = Self {
+            nodes_created: count("nodes-created"),
+            nodes_deleted: count("nodes-deleted"),
+            relationships_created: count("relationships-created"),
+            relationships_deleted: count("relationships-deleted"),
+            properties_set: count("properties-set"),
+            labels_added: count("labels-added"),
+            labels_removed: count("labels-removed"),
+            indexes_added: count("indexes-added"),
+            indexes_removed: count("indexes-removed"),
+            constraints_added: count("constraints-added"),
+            constraints_removed: count("constraints-removed"),
+            system_updates: count("system-updates"),
+            contains_updates: stats.get::<bool>("contains-updates").unwrap_or(false),
+            contains_system_updates: stats
+                .get::<bool>("contains-system-updates")
+                .unwrap_or(false),
+        };
+        // older servers only send the counters that are not zero, but not the flags
+        counters.contains_updates |= [
+            counters.nodes_created,
+            counters.nodes_deleted,
+            counters.relationships_created,
+            counters.relationships_deleted,
+            counters.properties_set,
+            counters.labels_added,
+            counters.labels_removed,
+            counters.indexes_added,
+            counters.indexes_removed,
+            counters.constraints_added,
+            counters.constraints_removed,
+        ]
+        .iter()
+        .any(|count| *count > 0);
+        counters.contains_system_updates |= counters.system_updates > 0;
+        counters
+    }
+
+    pub fn nodes_created(&self) -> u64 {
+        self.nodes_created
+    }
+
+    pub fn nodes_deleted(&self) -> u64 {
+        self.nodes_deleted
+    }
+
+    pub fn relationships_created(&self) -> u64 {
+        self.relationships_created
+    }
+
+    pub fn relationships_deleted(&self) -> u64 {
+        self.relationships_deleted
+    }
+
+    pub fn properties_set(&self) -> u64 {
+        self.properties_set
+    }
+
+    pub fn labels_added(&self) -> u64 {
+        self.labels_added
+    }
+
+    pub fn labels_removed(&self) -> u64 {
+        self.labels_removed
+    }
+
+    pub fn indexes_added(&self) -> u64 {
+        self.indexes_added
+    }
+
+    pub fn indexes_removed(&self) -> u64 {
+        self.indexes_removed
+    }
+
+    pub fn constraints_added(&self) -> u64 {
+        self.constraints_added
+    }
+
+    pub fn constraints_removed(&self) -> u64 {
+        self.constraints_removed
+    }
+
+    /// The number of changes to the system database, e.g. by creating users or databases.
+    pub fn system_updates(&self) -> u64 {
+        self.system_updates
+    }
+
+    /// Returns `true` if the query has changed the data or the schema of the database.
+    pub fn contains_updates(&self) -> bool {
+        self.contains_updates
+    }
+
+    /// Returns `true` if the query has changed the system database.
+    pub fn contains_system_updates(&self) -> bool {
+        self.contains_system_updates
+    }
+}
+
+/// Whether a query has read or written, see [`ResultSummary::query_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryType {
+    ReadOnly,
+    ReadWrite,
+    WriteOnly,
+    /// The query has only changed the schema, e.g. created an index.
+    SchemaWrite,
+}
+
+impl QueryType {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "r" => Some(QueryType::ReadOnly),
+            "rw" => Some(QueryType::ReadWrite),
+            "w" => Some(QueryType::WriteOnly),
+            "s" => Some(QueryType::SchemaWrite),
+            _ => None,
+        }
+    }
+}
+
+fn millis(millis: i64) -> Option<Duration> {
+    u64::try_from(millis).ok().map(Duration::from_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoltType;
+
+    fn server() -> BoltServer {
+        BoltServer::parse("localhost:7687").unwrap()
+    }
+
+    #[test]
+    fn should_read_the_summary() {
+        let stats = BoltMap::from_entries([
+            ("nodes-created", BoltType::from(2)),
+            ("properties-set", BoltType::from(3)),
+            ("labels-added", BoltType::from(1)),
+            ("contains-updates", BoltType::from(true)),
+        ]);
+        let metadata = BoltMap::from_entries([
+            ("type", BoltType::from("rw")),
+            ("db", BoltType::from("neo4j")),
+            ("t_last", BoltType::from(7)),
+            ("bookmark", BoltType::from("FB:kcwQ")),
+            ("stats", BoltType::Map(stats)),
+        ]);
+
+        let summary = ResultSummary::new(&server(), Some(42), &Success::new(metadata));
+
+        assert_eq!(summary.query_type(), Some(QueryType::ReadWrite));
+        assert_eq!(summary.database(), Some("neo4j"));
+        assert_eq!(summary.server_address(), "localhost:7687");
+        assert_eq!(
+            summary.result_available_after(),
+            Some(Duration::from_millis(42))
+        );
+        assert_eq!(
+            summary.result_consumed_after(),
+            Some(Duration::from_millis(7))
+        );
+        let counters = summary.counters();
+        assert_eq!(counters.nodes_created(), 2);
+        assert_eq!(counters.properties_set(), 3);
+        assert_eq!(counters.labels_added(), 1);
+        assert_eq!(counters.nodes_deleted(), 0);
+        assert!(counters.contains_updates());
+        assert!(!counters.contains_system_updates());
+    }
+
+    #[test]
+    fn should_tell_whether_a_query_contains_updates_without_the_flags() {
+        let stats = BoltMap::from_entries([("relationships-deleted", BoltType::from(1))]);
+        assert!(Counters::new(&stats).contains_updates());
+
+        let stats = BoltMap::from_entries([("system-updates", BoltType::from(1))]);
+        let counters = Counters::new(&stats);
+        assert!(!counters.contains_updates());
+        assert!(counters.contains_system_updates());
+
+        let summary = ResultSummary::new(&server(), None, &Success::new(BoltMap::default()));
+        assert_eq!(summary.counters(), &Counters::default());
+        assert_eq!(summary.query_type(), None);
+        assert_eq!(summary.result_available_after(), None);
+    }
+}
@@ -0,0 +1,63 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use super::BoltServer;
+use crate::{
+    config::PoolOptions,
+    connection::ConnectionInfo,
+    metrics::PoolMetrics,
+    pool::{build_pool, metrics, ConnectionPool},
+};
+
+/// Holds one connection pool for every server that is part of a routing table.
+pub(crate) struct ConnectionRegistry {
+    info: ConnectionInfo,
+    options: PoolOptions,
+    pools: Mutex<HashMap<BoltServer, ConnectionPool>>,
+}
+
+impl ConnectionRegistry {
+    pub(crate) fn new(info: ConnectionInfo, options: PoolOptions) -> Self {
+        Self {
+            info,
+            options,
+            pools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the pool for the given server, creating a new one if necessary.
+    pub(crate) fn get_or_create(&self, server: &BoltServer) -> ConnectionPool {
+        let mut pools = self.pools.lock().unwrap();
+        pools
+            .entry(server.clone())
+            .or_insert_with(|| {
+                log::debug!("creating connection pool for {server}");
+                build_pool(self.info.for_server(server), self.options)
+            })
+            .clone()
+    }
+
+    /// The metrics of the pools of all servers.
+    pub(crate) fn metrics(&self) -> Vec<PoolMetrics> {
+        self.pools.lock().unwrap().values().map(metrics).collect()
+    }
+
+    /// Closes and removes the pool for the given server.
+    pub(crate) fn remove(&self, server: &BoltServer) {
+        if let Some(pool) = self.pools.lock().unwrap().remove(server) {
+            log::debug!("closing connection pool for {server}");
+            pool.close();
+        }
+    }
+
+    /// Closes and removes all pools for which `keep` returns false.
+    pub(crate) fn retain(&self, mut keep: impl FnMut(&BoltServer) -> bool) {
+        self.pools.lock().unwrap().retain(|server, pool| {
+            let keep = keep(server);
+            if !keep {
+                log::debug!("closing connection pool for {server}");
+                pool.close();
+            }
+            keep
+        });
+    }
+}
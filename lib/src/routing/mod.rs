@@ -0,0 +1,7 @@
+mod connection_registry;
+mod routed_connection_manager;
+mod routing_table;
+
+pub(crate) use connection_registry::ConnectionRegistry;
+pub(crate) use routed_connection_manager::RoutedConnectionManager;
+pub(crate) use routing_table::{BoltServer, RoutingTable};
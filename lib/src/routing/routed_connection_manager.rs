@@ -0,0 +1,305 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        RwLock,
+    },
+};
+
+use backoff::ExponentialBackoff;
+use log::{debug, warn};
+
+use super::{routing_table::Routes, BoltServer, ConnectionRegistry, RoutingTable};
+use crate::{
+    auth::AuthToken,
+    config::{AccessMode, Database, PoolOptions},
+    connection::ConnectionInfo,
+    errors::{Error, Neo4jClientErrorKind, Neo4jErrorKind, Result},
+    messages::{BoltRequest, BoltResponse},
+    metrics::{PoolMetrics, RetryCounter},
+    pool::{acquire, default_backoff, warm_up, ManagedConnection},
+    query::Query,
+    BoltType, Version,
+};
+
+const ROUTING_TABLE_PROCEDURE: &str = "CALL dbms.routing.getRoutingTable($context, $database)";
+const SYSTEM_DATABASE: &str = "system";
+const ROUTING_TABLE_FETCH_SIZE: usize = 100;
+
+/// Selects a server from the routing table of a database and
+/// acquires a connection from the pool of that server.
+///
+/// Routing tables are fetched from the routers of the cluster, starting with the
+/// address from the URI, and refreshed whenever their time-to-live has elapsed.
+/// The table of the home database is stored under its resolved name, or under `None`
+/// if the server does not report the name.
+pub(crate) struct RoutedConnectionManager {
+    info: ConnectionInfo,
+    initial_router: BoltServer,
+    tables: RwLock<HashMap<Option<Database>, Routes>>,
+    refresh: tokio::sync::Mutex<()>,
+    registry: ConnectionRegistry,
+    backoff: ExponentialBackoff,
+    next: AtomicUsize,
+    min_connections: usize,
+    retries: RetryCounter,
+}
+
+impl RoutedConnectionManager {
+    pub(crate) fn new(info: ConnectionInfo, options: PoolOptions) -> Self {
+        let initial_router = info.server();
+        let registry = ConnectionRegistry::new(info.clone(), options);
+        Self {
+            info,
+            initial_router,
+            tables: RwLock::new(HashMap::new()),
+            refresh: tokio::sync::Mutex::new(()),
+            registry,
+            backoff: default_backoff(),
+            next: AtomicUsize::new(0),
+            min_connections: options.min_connections,
+            retries: RetryCounter::default(),
+        }
+    }
+
+    /// Opens the minimum number of connections to the router from the URI.
+    /// The pools of the other servers are filled once the routing table is known.
+    pub(crate) async fn warm_up(&self) -> Result<()> {
+        let pool = self.registry.get_or_create(&self.initial_router);
+        warm_up(&pool, self.min_connections).await
+    }
+
+    pub(crate) fn backoff(&self) -> ExponentialBackoff {
+        self.backoff.clone()
+    }
+
+    pub(crate) fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+
+    pub(crate) fn retries(&self) -> &RetryCounter {
+        &self.retries
+    }
+
+    pub(crate) fn pool_metrics(&self) -> Vec<PoolMetrics> {
+        self.registry.metrics()
+    }
+
+    /// Acquires a connection to a server that can handle the operation on the given database.
+    ///
+    /// Servers that cannot be reached are removed from the routing table and the
+    /// next server of the same role is tried.
+    pub(crate) async fn get(
+        &self,
+        db: Option<&str>,
+        mode: AccessMode,
+        auth: Option<&AuthToken>,
+        bookmarks: &[String],
+    ) -> Result<ManagedConnection> {
+        let servers = self.servers(db, mode, bookmarks).await?;
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        for i in 0..servers.len() {
+            let server = &servers[(start + i) % servers.len()];
+            match acquire(&self.registry.get_or_create(server), auth).await {
+                Ok(connection) => return Ok(connection),
+                Err(e) => match e {
+                    e @ (Error::IOError { .. }
+                    | Error::ConnectionError
+                    | Error::ConnectTimeout
+                    | Error::HandshakeTimeout) => {
+                        warn!("Failed to connect to {server}, removing it from the routing table: {e}");
+                        self.forget(server);
+                    }
+                    e => return Err(e),
+                },
+            }
+        }
+
+        Err(Error::ServiceUnavailable(format!(
+            "no {mode} server available for database `{}`",
+            db.unwrap_or("<home>")
+        )))
+    }
+
+    /// Resolves the name of the home database of the user with a routing table request
+    /// that does not specify a database, and keeps the table for later requests.
+    pub(crate) async fn resolve_home_db(
+        &self,
+        imp_user: Option<&str>,
+        bookmarks: &[String],
+    ) -> Result<Option<Database>> {
+        let _refresh = self.refresh.lock().await;
+        let mut routers = Vec::new();
+        for routes in self.tables.read().unwrap().values() {
+            for router in routes.routers() {
+                if !routers.contains(router) {
+                    routers.push(router.clone());
+                }
+            }
+        }
+
+        let routes = self
+            .fetch_routes(None, imp_user, bookmarks, routers)
+            .await?;
+        let db = routes.database().cloned();
+        self.insert(db.clone(), routes);
+        Ok(db)
+    }
+
+    /// Marks the routing table of the database as stale if the error indicates
+    /// that the cluster topology has changed, e.g. after a leader switch.
+    pub(crate) fn on_error(&self, db: Option<&str>, error: &Error) {
+        let invalidate = match error {
+            Error::IOError { .. }
+            | Error::ConnectionError
+            | Error::ConnectTimeout
+            | Error::HandshakeTimeout
+            | Error::ReadTimeout => true,
+            Error::Neo4j(e) => matches!(
+                e.kind(),
+                Neo4jErrorKind::Client(
+                    Neo4jClientErrorKind::SessionExpired | Neo4jClientErrorKind::FatalDiscovery
+                )
+            ),
+            _ => false,
+        };
+        if invalidate {
+            let key = db.map(Database::from);
+            if let Some(routes) = self.tables.write().unwrap().get_mut(&key) {
+                debug!("invalidating routing table for database `{db:?}` due to: {error}");
+                routes.expire();
+            }
+        }
+    }
+
+    fn forget(&self, server: &BoltServer) {
+        for routes in self.tables.write().unwrap().values_mut() {
+            routes.forget(server);
+        }
+        self.registry.remove(server);
+    }
+
+    async fn servers(
+        &self,
+        db: Option<&str>,
+        mode: AccessMode,
+        bookmarks: &[String],
+    ) -> Result<Vec<BoltServer>> {
+        if let Some(servers) = self.fresh_servers(db, mode) {
+            return Ok(servers);
+        }
+
+        let _refresh = self.refresh.lock().await;
+        // another task might have refreshed the table while we were waiting for the lock
+        if let Some(servers) = self.fresh_servers(db, mode) {
+            return Ok(servers);
+        }
+
+        let routers = self
+            .tables
+            .read()
+            .unwrap()
+            .get(&db.map(Database::from))
+            .map(|routes| routes.routers().to_vec())
+            .unwrap_or_default();
+
+        let routes = self.fetch_routes(db, None, bookmarks, routers).await?;
+        let servers = routes.servers(mode).to_vec();
+        self.insert(db.map(Database::from), routes);
+
+        Ok(servers)
+    }
+
+    fn insert(&self, db: Option<Database>, routes: Routes) {
+        let mut tables = self.tables.write().unwrap();
+        tables.insert(db, routes);
+        self.registry.retain(|server| {
+            *server == self.initial_router || tables.values().any(|routes| routes.contains(server))
+        });
+    }
+
+    fn fresh_servers(&self, db: Option<&str>, mode: AccessMode) -> Option<Vec<BoltServer>> {
+        let tables = self.tables.read().unwrap();
+        tables
+            .get(&db.map(Database::from))
+            .filter(|routes| !routes.is_stale(mode))
+            .map(|routes| routes.servers(mode).to_vec())
+    }
+
+    async fn fetch_routes(
+        &self,
+        db: Option<&str>,
+        imp_user: Option<&str>,
+        bookmarks: &[String],
+        mut routers: Vec<BoltServer>,
+    ) -> Result<Routes> {
+        if !routers.contains(&self.initial_router) {
+            routers.push(self.initial_router.clone());
+        }
+
+        let mut last_error = None;
+        for router in routers {
+            debug!("fetching routing table for database `{db:?}` from {router}");
+            let result = match acquire(&self.registry.get_or_create(&router), None).await {
+                Ok(mut connection) => {
+                    self.fetch_routing_table(&mut connection, db, imp_user, bookmarks)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match result.and_then(Routes::new) {
+                Ok(routes) => return Ok(routes),
+                Err(Error::Neo4j(e)) if !e.can_retry() => return Err(Error::Neo4j(e)),
+                Err(e) => {
+                    warn!("Failed to fetch routing table from {router}: {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(Error::RoutingTableError(match last_error {
+            Some(e) => format!("no router could provide a routing table, last error: {e}"),
+            None => "no router could provide a routing table".into(),
+        }))
+    }
+
+    async fn fetch_routing_table(
+        &self,
+        connection: &mut ManagedConnection,
+        db: Option<&str>,
+        imp_user: Option<&str>,
+        bookmarks: &[String],
+    ) -> Result<RoutingTable> {
+        let context = self.info.routing_context().cloned().unwrap_or_default();
+        let version = connection.version();
+
+        if version >= Version::V4_3 {
+            let route = BoltRequest::route(context, bookmarks, db, imp_user, version);
+            return match connection.send_recv(route).await? {
+                BoltResponse::Success(success) => success
+                    .get::<RoutingTable>("rt")
+                    .map_err(Error::DeserializationError),
+                msg => Err(msg.into_error("ROUTE")),
+            };
+        }
+
+        let query = Query::new(ROUTING_TABLE_PROCEDURE.to_owned())
+            .param("context", BoltType::Map(context))
+            .param("database", db);
+        let mut stream = query
+            .execute_mut(Some(SYSTEM_DATABASE), ROUTING_TABLE_FETCH_SIZE, connection)
+            .await?;
+
+        let mut table = None;
+        while let Some(row) = stream.next(&mut *connection).await? {
+            table.get_or_insert(row);
+        }
+
+        table
+            .ok_or_else(|| {
+                Error::RoutingTableError("the routing procedure returned no rows".into())
+            })?
+            .to::<RoutingTable>()
+            .map_err(Error::DeserializationError)
+    }
+}
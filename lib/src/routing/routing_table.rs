@@ -0,0 +1,257 @@
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use url::Host;
+
+use crate::{
+    config::{AccessMode, Database},
+    errors::{Error, Result},
+};
+
+/// The routing table as it is returned by the server,
+/// either from the `ROUTE` message or from the `dbms.routing.getRoutingTable` procedure.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct RoutingTable {
+    pub(crate) ttl: u64,
+    #[serde(default)]
+    pub(crate) db: Option<String>,
+    pub(crate) servers: Vec<Server>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Server {
+    pub(crate) addresses: Vec<String>,
+    pub(crate) role: Role,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(crate) enum Role {
+    Route,
+    Read,
+    Write,
+}
+
+/// The address of a single server in the cluster.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct BoltServer {
+    pub(crate) host: Host<Arc<str>>,
+    pub(crate) port: u16,
+}
+
+impl BoltServer {
+    pub(crate) fn new(host: Host<Arc<str>>, port: u16) -> Self {
+        Self { host, port }
+    }
+
+    /// Parses an address in the form of `host:port` as it is sent by the server.
+    pub(crate) fn parse(address: &str) -> Result<Self> {
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) if !host.ends_with(':') => {
+                let port = port
+                    .parse()
+                    .map_err(|_| Error::RoutingTableError(format!("invalid address: {address}")))?;
+                (host, port)
+            }
+            _ => (address, 7687),
+        };
+        let host = match Host::parse(host)? {
+            Host::Domain(domain) => Host::Domain(Arc::<str>::from(domain)),
+            Host::Ipv4(ip) => Host::Ipv4(ip),
+            Host::Ipv6(ip) => Host::Ipv6(ip),
+        };
+        Ok(Self { host, port })
+    }
+}
+
+impl fmt::Display for BoltServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// The servers of a routing table for a single database,
+/// grouped by their role and valid until the time-to-live has elapsed.
+#[derive(Debug, Clone)]
+pub(crate) struct Routes {
+    db: Option<Database>,
+    routers: Vec<BoltServer>,
+    readers: Vec<BoltServer>,
+    writers: Vec<BoltServer>,
+    expires_at: Instant,
+}
+
+impl Routes {
+    pub(crate) fn new(table: RoutingTable) -> Result<Self> {
+        let mut routers = Vec::new();
+        let mut readers = Vec::new();
+        let mut writers = Vec::new();
+        for server in table.servers {
+            let target = match server.role {
+                Role::Route => &mut routers,
+                Role::Read => &mut readers,
+                Role::Write => &mut writers,
+            };
+            for address in server.addresses {
+                target.push(BoltServer::parse(&address)?);
+            }
+        }
+
+        if routers.is_empty() {
+            return Err(Error::RoutingTableError(
+                "the routing table does not contain any routers".into(),
+            ));
+        }
+
+        let expires_at = Instant::now()
+            .checked_add(Duration::from_secs(table.ttl))
+            .unwrap_or_else(|| Instant::now() + Duration::from_secs(u32::MAX.into()));
+
+        Ok(Self {
+            db: table.db.map(Database::from),
+            routers,
+            readers,
+            writers,
+            expires_at,
+        })
+    }
+
+    /// The name of the database, as resolved by the server.
+    pub(crate) fn database(&self) -> Option<&Database> {
+        self.db.as_ref()
+    }
+
+    pub(crate) fn routers(&self) -> &[BoltServer] {
+        &self.routers
+    }
+
+    pub(crate) fn servers(&self, mode: AccessMode) -> &[BoltServer] {
+        match mode {
+            AccessMode::Read => &self.readers,
+            AccessMode::Write => &self.writers,
+        }
+    }
+
+    /// Returns true if the table must be refreshed before it can serve the given operation.
+    pub(crate) fn is_stale(&self, mode: AccessMode) -> bool {
+        Instant::now() >= self.expires_at
+            || self.routers.is_empty()
+            || self.servers(mode).is_empty()
+    }
+
+    pub(crate) fn contains(&self, server: &BoltServer) -> bool {
+        self.routers.contains(server)
+            || self.readers.contains(server)
+            || self.writers.contains(server)
+    }
+
+    /// Removes a server that could not be reached from all roles.
+    pub(crate) fn forget(&mut self, server: &BoltServer) {
+        self.routers.retain(|s| s != server);
+        self.readers.retain(|s| s != server);
+        self.writers.retain(|s| s != server);
+    }
+
+    pub(crate) fn expire(&mut self) {
+        self.expires_at = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+    use crate::{BoltList, BoltMap, BoltType};
+
+    fn server(role: &str, addresses: &[&str]) -> BoltType {
+        let addresses = addresses
+            .iter()
+            .map(|&a| BoltType::from(a))
+            .collect::<Vec<_>>();
+        BoltType::Map(BoltMap::from_entries([
+            ("addresses", BoltType::List(BoltList::from(addresses))),
+            ("role", role.into()),
+        ]))
+    }
+
+    #[test]
+    fn should_parse_addresses() {
+        let server = BoltServer::parse("core1.example.com:7688").unwrap();
+        assert_eq!(server.host, Host::Domain("core1.example.com".into()));
+        assert_eq!(server.port, 7688);
+
+        let server = BoltServer::parse("10.0.0.1:7687").unwrap();
+        assert_eq!(server.host, Host::Ipv4(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(server.port, 7687);
+
+        let server = BoltServer::parse("[::1]:9001").unwrap();
+        assert_eq!(server.host, Host::Ipv6(Ipv6Addr::LOCALHOST));
+        assert_eq!(server.port, 9001);
+        assert_eq!(server.to_string(), "[::1]:9001");
+
+        let server = BoltServer::parse("localhost").unwrap();
+        assert_eq!(server.port, 7687);
+
+        assert!(BoltServer::parse("localhost:port").is_err());
+    }
+
+    #[test]
+    fn should_deserialize_routing_table() {
+        let rt = BoltMap::from_entries([
+            ("ttl", 300.into()),
+            ("db", "neo4j".into()),
+            (
+                "servers",
+                BoltType::List(BoltList::from(vec![
+                    server("WRITE", &["core1:7687"]),
+                    server("READ", &["core2:7687", "core3:7687"]),
+                    server("ROUTE", &["core1:7687", "core2:7687", "core3:7687"]),
+                ])),
+            ),
+        ]);
+
+        let table = BoltType::Map(rt).to::<RoutingTable>().unwrap();
+        assert_eq!(table.ttl, 300);
+        assert_eq!(table.db.as_deref(), Some("neo4j"));
+        assert_eq!(table.servers.len(), 3);
+        assert_eq!(table.servers[0].role, Role::Write);
+
+        let mut routes = Routes::new(table).unwrap();
+        assert_eq!(routes.database().map(|db| &**db), Some("neo4j"));
+        assert_eq!(routes.routers().len(), 3);
+        assert_eq!(routes.servers(AccessMode::Read).len(), 2);
+        assert_eq!(routes.servers(AccessMode::Write).len(), 1);
+        assert!(!routes.is_stale(AccessMode::Write));
+
+        let writer = BoltServer::parse("core1:7687").unwrap();
+        routes.forget(&writer);
+        assert!(!routes.contains(&writer));
+        assert!(routes.is_stale(AccessMode::Write));
+        assert!(!routes.is_stale(AccessMode::Read));
+
+        routes.expire();
+        assert!(routes.is_stale(AccessMode::Read));
+    }
+
+    #[test]
+    fn should_reject_table_without_routers() {
+        let table = RoutingTable {
+            ttl: 300,
+            db: None,
+            servers: vec![Server {
+                addresses: vec!["core1:7687".into()],
+                role: Role::Write,
+            }],
+        };
+
+        assert!(matches!(
+            Routes::new(table),
+            Err(Error::RoutingTableError(_))
+        ));
+    }
+}
@@ -151,7 +151,7 @@ impl Point3D {
 impl Row {
     pub fn new(fields: BoltList, data: BoltList) -> Self {
         let mut attributes = BoltMap::with_capacity(fields.len());
-        for (field, value) in fields.into_iter().zip(data.into_iter()) {
+        for (field, value) in fields.into_iter().zip(data) {
             if let Ok(key) = field.try_into() {
                 attributes.put(key, value);
             }
@@ -204,6 +204,11 @@ impl Node {
         self.inner.id.value
     }
 
+    /// Element id of the node, only sent by servers since Bolt 5.0
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id.as_ref().map(|id| id.value.as_str())
+    }
+
     /// various labels attached to this node
     pub fn labels(&self) -> Vec<&str> {
         self.to::<crate::Labels<_>>().unwrap().0
@@ -248,6 +253,27 @@ impl Relation {
         self.inner.end_node_id.value
     }
 
+    /// Element id of the relationship, only sent by servers since Bolt 5.0
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id.as_ref().map(|id| id.value.as_str())
+    }
+
+    /// Element id of the start node, only sent by servers since Bolt 5.0
+    pub fn start_node_element_id(&self) -> Option<&str> {
+        self.inner
+            .start_node_element_id
+            .as_ref()
+            .map(|id| id.value.as_str())
+    }
+
+    /// Element id of the end node, only sent by servers since Bolt 5.0
+    pub fn end_node_element_id(&self) -> Option<&str> {
+        self.inner
+            .end_node_element_id
+            .as_ref()
+            .map(|id| id.value.as_str())
+    }
+
     pub fn typ(&self) -> &str {
         self.to::<crate::Type<_>>().unwrap().0
     }
@@ -283,6 +309,11 @@ impl UnboundedRelation {
         self.inner.id.value
     }
 
+    /// Element id of the relationship, only sent by servers since Bolt 5.0
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id.as_ref().map(|id| id.value.as_str())
+    }
+
     pub fn typ(&self) -> &str {
         self.to::<crate::Type<_>>().unwrap().0
     }
@@ -0,0 +1,410 @@
+use std::{sync::Arc, time::Duration};
+
+use backoff::backoff::Backoff as _;
+use futures::future::BoxFuture;
+
+use crate::{
+    auth::AuthToken,
+    bookmarks::{BookmarkManager, Bookmarks},
+    config::{AccessMode, Database, SessionConfig, TxConfig},
+    errors::{Error, Neo4jClientErrorKind, Neo4jErrorKind, Result},
+    home_db::{HomeDatabase, HomeDatabases},
+    notification::NotificationFilter,
+    pool::{ConnectionPoolManager, ManagedConnection},
+    query::{retryable, Query},
+    result_summary::ResultSummary,
+    stream::DetachedRowStream,
+    telemetry,
+    txn::Txn,
+};
+
+/// A context for a sequence of transactions, created with [`crate::Graph::session`].
+///
+/// All work of a session runs on the same database, with the same default access mode
+/// and on behalf of the same impersonated user, as configured by the [`crate::SessionConfig`].
+/// Every transaction of the session waits for the bookmarks of the previous one,
+/// so that it sees all changes made earlier in the session, even on a cluster.
+///
+/// Without a configured database, the session runs on the home database of the user.
+/// The home database is resolved on first use and the session stays on it afterwards.
+///
+/// With [`crate::SessionConfig::auth`], all work of the session runs as another user.
+/// Pooled connections switch to the credentials of the session while it uses them,
+/// and switch back before they are handed out to anybody else.
+///
+/// Sessions are cheap to create and hold no connection while no query is running.
+#[derive(Clone)]
+pub struct Session {
+    pool: ConnectionPoolManager,
+    db: Option<Database>,
+    home_db: Option<HomeDatabase>,
+    access_mode: AccessMode,
+    fetch_size: usize,
+    imp_user: Option<String>,
+    auth: Option<AuthToken>,
+    notification_filter: Option<NotificationFilter>,
+    bookmark_manager: Arc<dyn BookmarkManager>,
+}
+
+impl Session {
+    /// Creates a session for the given config, except for its bookmarks,
+    /// which are tracked by the `bookmark_manager` instead.
+    pub(crate) fn new(
+        pool: ConnectionPoolManager,
+        home_dbs: &Arc<HomeDatabases>,
+        config: SessionConfig,
+        fetch_size: usize,
+        bookmark_manager: Arc<dyn BookmarkManager>,
+    ) -> Self {
+        let home_db = match (&config.db, &config.auth) {
+            (Some(_), _) => None,
+            (None, None) => Some(HomeDatabase::new(home_dbs.clone(), config.imp_user.clone())),
+            // the shared cache only knows the home databases of the users of the pool
+            (None, Some(_)) => Some(HomeDatabase::new(Arc::default(), config.imp_user.clone())),
+        };
+        Self {
+            pool,
+            db: config.db,
+            home_db,
+            access_mode: config.access_mode,
+            fetch_size: config.fetch_size.unwrap_or(fetch_size),
+            imp_user: config.imp_user,
+            auth: config.auth,
+            notification_filter: config.notification_filter,
+            bookmark_manager,
+        }
+    }
+
+    /// The database that this session runs on,
+    /// or `None` if the home database of the user has not been resolved yet.
+    pub fn database(&self) -> Option<Database> {
+        match &self.home_db {
+            Some(home_db) => home_db.get(),
+            None => self.db.clone(),
+        }
+    }
+
+    /// The bookmarks of the last transaction of this session.
+    /// Pass them to [`crate::SessionConfig::bookmarks`] to continue the causal chain
+    /// in another session.
+    pub fn last_bookmarks(&self) -> Vec<String> {
+        self.bookmark_manager.get_bookmarks()
+    }
+
+    /// Starts a new transaction.
+    /// All queries that needs to be run/executed within the transaction
+    /// should be executed using either [`Txn::run`] or [`Txn::execute`]
+    ///
+    /// Transactions will not be automatically retried on any failure,
+    /// use [`Session::execute_write`] or [`Session::execute_read`] for that.
+    pub async fn start_txn(&self) -> Result<Txn> {
+        self.start_txn_with(TxConfig::default()).await
+    }
+
+    /// Starts a new transaction configured by the given [`TxConfig`].
+    ///
+    /// Transactions will not be automatically retried on any failure.
+    pub async fn start_txn_with(&self, config: TxConfig) -> Result<Txn> {
+        self.start_txn_inner(&self.tx_config(config)).await
+    }
+
+    async fn start_txn_inner(&self, config: &TxConfig) -> Result<Txn> {
+        let bookmarks = self.bookmarks();
+        let db = self.resolve_database(config, &bookmarks).await?;
+        let connection = self
+            .connection(db.as_deref(), config.mode(), &bookmarks)
+            .await?;
+        Txn::new(db, self.fetch_size, bookmarks, config, connection).await
+    }
+
+    /// Runs a query in an auto-commit transaction, discards the result and returns its summary.
+    ///
+    /// This operation retires the query on certain failures.
+    /// All errors with the `Transient` error class as well as a few other error classes are considered retryable.
+    /// This includes errors during a leader election or when the transaction resources on the server (memory, handles, ...) are exhausted.
+    /// Retries happen with an exponential backoff until a retry delay exceeds 60s, at which point the query fails with the last error as it would without any retry.
+    pub async fn run(&self, q: Query) -> Result<ResultSummary> {
+        self.run_with(q, TxConfig::default()).await
+    }
+
+    /// Runs a query in an auto-commit transaction configured by the given [`TxConfig`].
+    ///
+    /// This operation retries the query on certain failures, see [`Session::run`].
+    pub async fn run_with(&self, q: Query, config: TxConfig) -> Result<ResultSummary> {
+        let config = self.tx_config(config);
+        let mut attempt = 0;
+        backoff::future::retry_notify(
+            self.pool.backoff(),
+            || {
+                attempt += 1;
+                let query = &q;
+                let config = &config;
+                telemetry::instrument(telemetry::attempt(attempt), async move {
+                    let bookmarks = self.bookmarks();
+                    let db = self
+                        .resolve_database(config, &bookmarks)
+                        .await
+                        .map_err(retryable)?;
+                    let db = db.as_deref();
+                    let mut connection = self
+                        .connection(db, config.mode(), &bookmarks)
+                        .await
+                        .map_err(|e| self.on_error(db, backoff::Error::permanent(e)))?;
+                    let home_db = self.unresolved_home_db(db, config);
+                    query
+                        .run_retryable(db, &bookmarks, home_db, config, &mut connection)
+                        .await
+                        .map_err(|e| self.on_error(db, e))
+                })
+            },
+            |e, delay| self.log_retry(e, delay),
+        )
+        .await
+    }
+
+    /// Executes a query in an auto-commit transaction and returns a [`DetachedRowStream`]
+    ///
+    /// This operation retries the query on certain failures, see [`Session::run`].
+    pub async fn execute(&self, q: Query) -> Result<DetachedRowStream> {
+        self.execute_with(q, TxConfig::default()).await
+    }
+
+    /// Executes a query in an auto-commit transaction configured by the given [`TxConfig`]
+    /// and returns a [`DetachedRowStream`].
+    ///
+    /// This operation retries the query on certain failures, see [`Session::run`].
+    pub async fn execute_with(&self, q: Query, config: TxConfig) -> Result<DetachedRowStream> {
+        let config = self.tx_config(config);
+        let mut attempt = 0;
+        backoff::future::retry_notify(
+            self.pool.backoff(),
+            || {
+                attempt += 1;
+                let fetch_size = self.fetch_size;
+                let query = &q;
+                let config = &config;
+                telemetry::instrument(telemetry::attempt(attempt), async move {
+                    let bookmarks = self.bookmarks();
+                    let db = self
+                        .resolve_database(config, &bookmarks)
+                        .await
+                        .map_err(retryable)?;
+                    let db = db.as_deref();
+                    let connection = self
+                        .connection(db, config.mode(), &bookmarks)
+                        .await
+                        .map_err(|e| self.on_error(db, backoff::Error::permanent(e)))?;
+                    let home_db = self.unresolved_home_db(db, config).cloned();
+                    query
+                        .execute_retryable(db, fetch_size, bookmarks, home_db, config, connection)
+                        .await
+                        .map_err(|e| self.on_error(db, e))
+                })
+            },
+            |e, delay| self.log_retry(e, delay),
+        )
+        .await
+    }
+
+    /// Runs the given transaction function in a write transaction.
+    ///
+    /// See [`crate::Graph::execute_write`] for how the transaction is completed and retried.
+    pub async fn execute_write<T, F>(&self, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        self.execute_write_with(TxConfig::default(), work).await
+    }
+
+    /// Runs the given transaction function in a write transaction configured by the given [`TxConfig`],
+    /// whose access mode is ignored.
+    ///
+    /// See [`crate::Graph::execute_write`] for how the transaction is completed and retried.
+    pub async fn execute_write_with<T, F>(&self, config: TxConfig, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        let config = config.access_mode(AccessMode::Write);
+        self.execute_transaction(&self.tx_config(config), work)
+            .await
+    }
+
+    /// Runs the given transaction function in a read transaction.
+    ///
+    /// See [`crate::Graph::execute_write`] for how the transaction is completed and retried.
+    pub async fn execute_read<T, F>(&self, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        self.execute_read_with(TxConfig::default(), work).await
+    }
+
+    /// Runs the given transaction function in a read transaction configured by the given [`TxConfig`],
+    /// whose access mode is ignored.
+    ///
+    /// See [`crate::Graph::execute_write`] for how the transaction is completed and retried.
+    pub async fn execute_read_with<T, F>(&self, config: TxConfig, work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        let config = config.access_mode(AccessMode::Read);
+        self.execute_transaction(&self.tx_config(config), work)
+            .await
+    }
+
+    async fn execute_transaction<T, F>(&self, config: &TxConfig, mut work: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        let mut backoff = self.pool.backoff();
+        backoff.reset();
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let span = telemetry::attempt(attempt);
+            let result = telemetry::instrument(span, self.try_transaction(config, &mut work)).await;
+            match result.map_err(|e| self.on_error(self.database().as_deref(), e)) {
+                Ok(value) => return Ok(value),
+                Err(backoff::Error::Permanent(e)) => return Err(e),
+                Err(backoff::Error::Transient { err, .. }) => match backoff.next_backoff() {
+                    Some(delay) => {
+                        self.log_retry(err, delay);
+                        tokio::time::sleep(delay).await;
+                    }
+                    None => return Err(err),
+                },
+            }
+        }
+    }
+
+    async fn try_transaction<T, F>(
+        &self,
+        config: &TxConfig,
+        work: &mut F,
+    ) -> Result<T, backoff::Error<Error>>
+    where
+        F: for<'t> FnMut(&'t mut Txn) -> BoxFuture<'t, Result<T>>,
+    {
+        let mut txn = self.start_txn_inner(config).await.map_err(retryable)?;
+        match work(&mut txn).await {
+            Ok(value) => {
+                txn.commit().await.map_err(retryable)?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Err(rollback) = txn.rollback().await {
+                    log::debug!("Failed to roll back transaction after error `{e}`: {rollback}");
+                }
+                Err(retryable(e))
+            }
+        }
+    }
+
+    /// Acquires a connection from the pool that is authenticated with the credentials of the session.
+    async fn connection(
+        &self,
+        db: Option<&str>,
+        mode: AccessMode,
+        bookmarks: &Bookmarks,
+    ) -> Result<ManagedConnection> {
+        self.pool
+            .get(db, mode, self.auth.as_ref(), bookmarks.used())
+            .await
+    }
+
+    /// The configured database, or the home database of the user if it can be resolved.
+    /// Otherwise, the database is omitted and the server picks the home database.
+    async fn resolve_database(
+        &self,
+        config: &TxConfig,
+        bookmarks: &Bookmarks,
+    ) -> Result<Option<Database>> {
+        if self.db.is_some() {
+            return Ok(self.db.clone());
+        }
+        let Some(home_db) = self.home_db_for(config) else {
+            return Ok(None);
+        };
+        if self.auth.is_none() {
+            home_db.authenticated_with(&self.pool.credentials().await?.token);
+        }
+        if let Some(db) = home_db.get() {
+            return Ok(Some(db));
+        }
+        if self.auth.is_some() {
+            // the routing table would be fetched with the credentials of the pool,
+            // so the home database is resolved from the summary of the first query instead
+            return Ok(None);
+        }
+        let db = self
+            .pool
+            .resolve_home_db(home_db.imp_user(), bookmarks.used())
+            .await?;
+        if let Some(db) = &db {
+            home_db.resolved(db.clone());
+        }
+        Ok(db)
+    }
+
+    /// The home database of the session belongs to the impersonated user of the session,
+    /// and does not apply to transactions that impersonate somebody else.
+    fn home_db_for(&self, config: &TxConfig) -> Option<&HomeDatabase> {
+        self.home_db
+            .as_ref()
+            .filter(|home_db| home_db.imp_user() == config.imp_user.as_deref())
+    }
+
+    /// The home database that is resolved from the summary of a query without a database.
+    fn unresolved_home_db(&self, db: Option<&str>, config: &TxConfig) -> Option<&HomeDatabase> {
+        self.home_db_for(config).filter(|_| db.is_none())
+    }
+
+    fn on_error(&self, db: Option<&str>, error: backoff::Error<Error>) -> backoff::Error<Error> {
+        self.pool.on_error(db, &error);
+        // the auth manager of the pool is not responsible for the credentials of the session
+        let error = match self.auth {
+            Some(_) => error,
+            None => self.pool.on_security_error(error),
+        };
+        let (backoff::Error::Permanent(e) | backoff::Error::Transient { err: e, .. }) = &error;
+        if let (Some(home_db), Error::Neo4j(e)) = (&self.home_db, e) {
+            if e.kind() == Neo4jErrorKind::Client(Neo4jClientErrorKind::FatalDiscovery) {
+                home_db.invalidate();
+            }
+        }
+        error
+    }
+
+    /// Fills in the defaults of the session for everything the transaction does not override.
+    fn tx_config(&self, mut config: TxConfig) -> TxConfig {
+        config.access_mode.get_or_insert(self.access_mode);
+        if config.imp_user.is_none() {
+            config.imp_user.clone_from(&self.imp_user);
+        }
+        config.notification_filter = match (config.notification_filter, &self.notification_filter) {
+            (Some(filter), Some(defaults)) => Some(filter.or(defaults)),
+            (filter, defaults) => filter.or_else(|| defaults.clone()),
+        };
+        config
+    }
+
+    fn bookmarks(&self) -> Bookmarks {
+        Bookmarks::new(self.bookmark_manager.clone())
+    }
+
+    fn log_retry(&self, e: Error, delay: Duration) {
+        self.pool.retried();
+        telemetry::retry(&e, delay);
+        let level = match delay.as_millis() {
+            0..=499 => log::Level::Debug,
+            500..=4999 => log::Level::Info,
+            _ => log::Level::Warn,
+        };
+        log::log!(level, "Retrying query in {delay:?} due to error: {e}");
+    }
+}
+
+const _: () = {
+    const fn assert_send_sync<T: ?Sized + Send + Sync>() {}
+    assert_send_sync::<Session>();
+};
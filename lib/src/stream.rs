@@ -2,9 +2,12 @@
 use crate::summary::StreamingSummary;
 
 use crate::{
+    bookmarks::Bookmarks,
     errors::{Error, Result},
-    messages::{BoltRequest, BoltResponse},
+    home_db::HomeDatabase,
+    messages::{BoltRequest, BoltResponse, Success},
     pool::ManagedConnection,
+    result_summary::ResultSummary,
     row::Row,
     txn::TransactionHandle,
     types::BoltList,
@@ -27,18 +30,42 @@ pub struct RowStream {
     state: State,
     fetch_size: usize,
     buffer: VecDeque<Row>,
+    bookmarks: Option<Bookmarks>,
+    home_db: Option<HomeDatabase>,
+    /// The `t_first` of the success of RUN, for the summary.
+    t_first: Option<i64>,
+    summary: Option<ResultSummary>,
 }
 
 impl RowStream {
-    pub(crate) fn new(qid: i64, fields: BoltList, fetch_size: usize) -> Self {
+    pub(crate) fn new(qid: i64, fields: BoltList, fetch_size: usize, t_first: Option<i64>) -> Self {
         RowStream {
             qid,
             fields,
             fetch_size,
             state: State::Ready,
             buffer: VecDeque::with_capacity(fetch_size),
+            bookmarks: None,
+            home_db: None,
+            t_first,
+            summary: None,
         }
     }
+
+    /// The stream of an auto-commit transaction reports the bookmark
+    /// from its final summary once it has been consumed.
+    pub(crate) fn with_bookmarks(self, bookmarks: Bookmarks) -> Self {
+        RowStream {
+            bookmarks: Some(bookmarks),
+            ..self
+        }
+    }
+
+    /// The stream of a query that ran without a database resolves the home database
+    /// from its final summary.
+    pub(crate) fn with_home_database(self, home_db: Option<HomeDatabase>) -> Self {
+        RowStream { home_db, ..self }
+    }
 }
 
 /// An abstraction over a stream of rows, this is returned as a result of [`crate::Graph::execute`].
@@ -128,7 +155,7 @@ impl RowStream {
                                 break if s.get("has_more").unwrap_or(false) {
                                     State::Ready
                                 } else {
-                                    State::Complete(None)
+                                    self.complete(connection, &s)
                                 };
                             }
                             Ok(BoltResponse::Record(record)) => {
@@ -153,6 +180,42 @@ impl RowStream {
         }
     }
 
+    /// The summary of the query, once all rows have been consumed,
+    /// i.e. after [`RowStream::next`] returned `None`.
+    pub fn summary(&self) -> Option<&ResultSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Skips all rows that have not been consumed yet and returns the summary of the query.
+    ///
+    /// The rows that have not been fetched yet are discarded by the server
+    /// instead of being sent to the driver.
+    pub async fn consume(&mut self, mut handle: impl TransactionHandle) -> Result<ResultSummary> {
+        self.buffer.clear();
+        if self.state == State::Ready {
+            let connection = handle.connection();
+            self.state = match connection.send_recv(BoltRequest::discard(self.qid)).await? {
+                BoltResponse::Success(s) => self.complete(connection, &s),
+                msg => return Err(msg.into_error("DISCARD")),
+            };
+        }
+        self.summary
+            .clone()
+            .ok_or_else(|| Error::UnexpectedMessage("the result has no summary".into()))
+    }
+
+    /// Takes the summary from the success of the PULL or DISCARD that completed the result.
+    fn complete(&mut self, connection: &ManagedConnection, success: &Success) -> State {
+        if let Some(bookmarks) = self.bookmarks.take() {
+            bookmarks.update(success);
+        }
+        if let Some(home_db) = self.home_db.take() {
+            home_db.update(success);
+        }
+        self.summary = Some(connection.summary(self.t_first, success));
+        State::Complete(None)
+    }
+
     /// Turns this RowStream into a [`futures::stream::TryStream`] where
     /// every element is a [`crate::row::Row`].
     pub fn into_stream(
@@ -231,6 +294,18 @@ impl DetachedRowStream {
         self.stream.next_or_summary(&mut self.connection).await
     }
 
+    /// The summary of the query, once all rows have been consumed,
+    /// i.e. after [`DetachedRowStream::next`] returned `None`.
+    pub fn summary(&self) -> Option<&ResultSummary> {
+        self.stream.summary()
+    }
+
+    /// Skips all rows that have not been consumed yet and returns the summary of the query,
+    /// see [`RowStream::consume`].
+    pub async fn consume(&mut self) -> Result<ResultSummary> {
+        self.stream.consume(&mut self.connection).await
+    }
+
     /// Turns this RowStream into a [`futures::stream::TryStream`] where
     /// every element is a [`crate::row::Row`].
     pub fn into_stream(self) -> impl TryStream<Ok = Row, Error = Error> {
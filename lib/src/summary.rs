@@ -5,6 +5,8 @@ use serde::{
     Deserialize,
 };
 
+use crate::notification::{self, GqlStatusObject, Notification};
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Streaming {
     HasMore,
@@ -28,7 +30,19 @@ pub struct StreamingSummary {
     pub(crate) stats: Option<crate::BoltMap>,
     pub(crate) plan: Option<crate::BoltMap>,
     pub(crate) profile: Option<crate::BoltMap>,
-    pub(crate) notifications: Option<Vec<crate::BoltMap>>,
+    pub(crate) notifications: Vec<Notification>,
+    pub(crate) gql_status_objects: Vec<GqlStatusObject>,
+}
+
+impl StreamingSummary {
+    pub fn notifications(&self) -> &[Notification] {
+        &self.notifications
+    }
+
+    /// Only sent by servers with Bolt 5.5 or later.
+    pub fn gql_status_objects(&self) -> &[GqlStatusObject] {
+        &self.gql_status_objects
+    }
 }
 
 impl<'de> Deserialize<'de> for Type {
@@ -92,6 +106,7 @@ impl<'de> Deserialize<'de> for Streaming {
             "plan",
             "profile",
             "notifications",
+            "statuses",
         ];
 
         struct Visit;
@@ -152,6 +167,7 @@ impl<'de> Deserialize<'de> for Streaming {
                     plan,
                     profile,
                     notifications,
+                    statuses,
                 );
 
                 let has_more = has_more.unwrap_or(false);
@@ -163,6 +179,8 @@ impl<'de> Deserialize<'de> for Streaming {
                 let t_last = t_last.ok_or_else(|| de::Error::missing_field("t_last"))?;
                 let r#type = r#type.ok_or_else(|| de::Error::missing_field("type"))?;
                 let db = db.ok_or_else(|| de::Error::missing_field("db"))?;
+                let (notifications, gql_status_objects) =
+                    notification::parse(notifications, statuses);
 
                 let full = StreamingSummary {
                     bookmark,
@@ -173,6 +191,7 @@ impl<'de> Deserialize<'de> for Streaming {
                     plan,
                     profile,
                     notifications,
+                    gql_status_objects,
                 };
 
                 Ok(Streaming::Done(Box::new(full)))
@@ -238,7 +257,8 @@ mod tests {
             ])),
             plan: None,
             profile: None,
-            notifications: None,
+            notifications: Vec::new(),
+            gql_status_objects: Vec::new(),
         };
 
         let actual = from_bytes::<Streaming>(data).unwrap();
@@ -0,0 +1,458 @@
+//! The spans of the `tracing` feature, which follow the OpenTelemetry semantic conventions
+//! for database clients, e.g. `db.system`, `db.namespace`, `db.operation.name`,
+//! `server.address` and `network.protocol.version`.
+//!
+//! Without the feature, all spans are no-ops that compile down to nothing.
+
+#[cfg(feature = "tracing")]
+pub(crate) use enabled::*;
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use disabled::*;
+
+#[cfg(feature = "tracing")]
+mod enabled {
+    use std::{collections::VecDeque, future::Future, time::Duration};
+
+    use tracing::{field::Empty, Instrument as _};
+
+    use crate::{
+        errors::{Error, Result},
+        messages::{BoltRequest, BoltResponse},
+        routing::BoltServer,
+        version::Version,
+    };
+
+    pub(crate) use tracing::Span;
+
+    const DB_SYSTEM: &str = "neo4j";
+
+    pub(crate) async fn instrument<F: Future>(span: Span, future: F) -> F::Output {
+        future.instrument(span).await
+    }
+
+    /// Covers opening a connection, from the TCP connect until the server accepted the credentials.
+    pub(crate) fn connect(server: &BoltServer) -> Span {
+        tracing::info_span!(
+            "neo4j.connect",
+            otel.kind = "client",
+            otel.status_code = Empty,
+            db.system = DB_SYSTEM,
+            server.address = %server.host,
+            server.port = server.port,
+            network.protocol.name = "bolt",
+            network.protocol.version = Empty,
+            error.type = Empty,
+        )
+    }
+
+    /// Covers the TLS handshake, if any, and the negotiation of the Bolt version.
+    pub(crate) fn handshake(server: &BoltServer) -> Span {
+        tracing::info_span!(
+            "neo4j.handshake",
+            otel.kind = "client",
+            otel.status_code = Empty,
+            db.system = DB_SYSTEM,
+            server.address = %server.host,
+            server.port = server.port,
+            network.protocol.name = "bolt",
+            network.protocol.version = Empty,
+            error.type = Empty,
+        )
+    }
+
+    /// Covers waiting for a connection of the pool, including opening a new one.
+    pub(crate) fn acquire(server: &BoltServer) -> Span {
+        tracing::info_span!(
+            "neo4j.acquire",
+            otel.status_code = Empty,
+            db.system = DB_SYSTEM,
+            server.address = %server.host,
+            server.port = server.port,
+            error.type = Empty,
+        )
+    }
+
+    /// Covers one attempt of a query or transaction that is retried on retryable errors,
+    /// starting with attempt 1.
+    pub(crate) fn attempt(attempt: u32) -> Span {
+        tracing::info_span!(
+            "neo4j.attempt",
+            db.system = DB_SYSTEM,
+            neo4j.attempt = attempt
+        )
+    }
+
+    pub(crate) fn negotiated(span: &Span, version: Version) {
+        span.record("network.protocol.version", protocol_version(version));
+    }
+
+    pub(crate) fn finished<T>(span: &Span, result: &Result<T>) {
+        if let Err(error) = result {
+            failed(span, &error_type(error));
+        }
+    }
+
+    pub(crate) fn retry(error: &Error, delay: Duration) {
+        tracing::info!(
+            error.type = %error_type(error),
+            neo4j.retry_delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+            "retrying after error: {error}"
+        );
+    }
+
+    fn failed(span: &Span, error_type: &str) {
+        span.record("error.type", error_type);
+        span.record("otel.status_code", "ERROR");
+    }
+
+    /// The Neo4j status code of a server error, otherwise the kind of the error.
+    fn error_type(error: &Error) -> String {
+        match error {
+            Error::Neo4j(error) => error.code().to_owned(),
+            // the name of the variant, without the details of the error
+            error => format!("{error:?}")
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap_or_default()
+                .to_owned(),
+        }
+    }
+
+    fn protocol_version(version: Version) -> &'static str {
+        match version {
+            Version::V4 => "4.0",
+            Version::V4_1 => "4.1",
+            Version::V4_2 => "4.2",
+            Version::V4_3 => "4.3",
+            Version::V4_4 => "4.4",
+            Version::V5_0 => "5.0",
+            Version::V5_1 => "5.1",
+            Version::V5_2 => "5.2",
+            Version::V5_3 => "5.3",
+            Version::V5_4 => "5.4",
+            Version::V5_5 => "5.5",
+            Version::V5_6 => "5.6",
+            Version::V5_7 => "5.7",
+        }
+    }
+
+    /// Follows the requests and responses of a connection with one span per round-trip,
+    /// from sending the request until its summary arrived.
+    #[derive(Debug)]
+    pub(crate) struct RequestSpans {
+        server: BoltServer,
+        version: Option<Version>,
+        query_text: bool,
+        in_txn: bool,
+        /// The database of the current transaction, `None` for the home database.
+        db: Option<String>,
+        /// The spans of the requests that the server has not responded to yet,
+        /// together with the number of records that PULL has received so far.
+        pending: VecDeque<(Span, Option<u64>)>,
+    }
+
+    impl RequestSpans {
+        pub(crate) fn new(server: BoltServer, query_text: bool) -> Self {
+            Self {
+                server,
+                version: None,
+                query_text,
+                in_txn: false,
+                db: None,
+                pending: VecDeque::new(),
+            }
+        }
+
+        pub(crate) fn negotiated(&mut self, version: Version) {
+            self.version = Some(version);
+        }
+
+        pub(crate) fn sent(&mut self, request: &BoltRequest) {
+            match request {
+                BoltRequest::Begin(_) => {
+                    self.in_txn = true;
+                    self.db = request.db().map(str::to_owned);
+                }
+                BoltRequest::Run(_) if !self.in_txn => self.db = request.db().map(str::to_owned),
+                _ => {}
+            }
+
+            let operation = request.name();
+            let otel_name = match &self.db {
+                Some(db) => format!("{operation} {db}"),
+                None => operation.to_owned(),
+            };
+            let span = tracing::info_span!(
+                "neo4j.request",
+                otel.name = %otel_name,
+                otel.kind = "client",
+                otel.status_code = Empty,
+                db.system = DB_SYSTEM,
+                db.namespace = self.db.as_deref(),
+                db.operation.name = operation,
+                db.query.text = Empty,
+                db.response.returned_rows = Empty,
+                db.response.status_code = Empty,
+                neo4j.qid = request.qid(),
+                server.address = %self.server.host,
+                server.port = self.server.port,
+                network.protocol.name = "bolt",
+                network.protocol.version = self.version.map(protocol_version),
+                error.type = Empty,
+            );
+            if self.query_text {
+                if let Some(query) = request.query() {
+                    span.record("db.query.text", query);
+                }
+            }
+
+            if let BoltRequest::Commit(_) | BoltRequest::Rollback(_) | BoltRequest::Reset(_) =
+                request
+            {
+                self.in_txn = false;
+            }
+            let rows = matches!(request, BoltRequest::Pull(_)).then_some(0);
+            self.pending.push_back((span, rows));
+        }
+
+        pub(crate) fn received(&mut self, response: &Result<BoltResponse>) {
+            match response {
+                Ok(BoltResponse::Record(_)) => {
+                    if let Some((_, Some(rows))) = self.pending.front_mut() {
+                        *rows += 1;
+                    }
+                }
+                Ok(BoltResponse::Success(success)) => {
+                    let Some((span, rows)) = self.pending.pop_front() else {
+                        return;
+                    };
+                    if let Ok(qid) = success.get::<i64>("qid") {
+                        span.record("neo4j.qid", qid);
+                    }
+                    if let Some(rows) = rows {
+                        span.record("db.response.returned_rows", rows);
+                    }
+                }
+                Ok(BoltResponse::Failure(failure)) => {
+                    let Some((span, _)) = self.pending.pop_front() else {
+                        return;
+                    };
+                    let code = failure.get::<String>("code").unwrap_or_default();
+                    span.record("db.response.status_code", code.as_str());
+                    failed(&span, &code);
+                }
+                Err(error) => {
+                    // no other response will arrive on this connection
+                    let error_type = error_type(error);
+                    for (span, _) in self.pending.drain(..) {
+                        failed(&span, &error_type);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod disabled {
+    use std::{future::Future, time::Duration};
+
+    use crate::{
+        errors::{Error, Result},
+        messages::{BoltRequest, BoltResponse},
+        routing::BoltServer,
+        version::Version,
+    };
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Span;
+
+    pub(crate) async fn instrument<F: Future>(_span: Span, future: F) -> F::Output {
+        future.await
+    }
+
+    pub(crate) fn connect(_server: &BoltServer) -> Span {
+        Span
+    }
+
+    pub(crate) fn handshake(_server: &BoltServer) -> Span {
+        Span
+    }
+
+    pub(crate) fn acquire(_server: &BoltServer) -> Span {
+        Span
+    }
+
+    pub(crate) fn attempt(_attempt: u32) -> Span {
+        Span
+    }
+
+    pub(crate) fn negotiated(_span: &Span, _version: Version) {}
+
+    pub(crate) fn finished<T>(_span: &Span, _result: &Result<T>) {}
+
+    pub(crate) fn retry(_error: &Error, _delay: Duration) {}
+
+    #[derive(Debug)]
+    pub(crate) struct RequestSpans;
+
+    impl RequestSpans {
+        pub(crate) fn new(_server: BoltServer, _query_text: bool) -> Self {
+            Self
+        }
+
+        pub(crate) fn negotiated(&mut self, _version: Version) {}
+
+        pub(crate) fn sent(&mut self, _request: &BoltRequest) {}
+
+        pub(crate) fn received(&mut self, _response: &Result<BoltResponse>) {}
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::{
+        collections::HashMap,
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    use bytes::Bytes;
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+
+    use super::*;
+    use crate::{
+        config::TxConfig,
+        errors::{Error, Result},
+        messages::{BoltRequest, BoltResponse, Success},
+        routing::BoltServer,
+        types::{BoltList, BoltMap, BoltType},
+        version::Version,
+    };
+
+    type Fields = HashMap<String, String>;
+
+    /// Records the fields of all spans, in the order they were created.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<Fields>>>);
+
+    struct Visitor<'a>(&'a mut Fields);
+
+    impl Visit for Visitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_owned(), value.to_owned());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name().to_owned(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Fields::new();
+            fields.insert("name".to_owned(), span.metadata().name().to_owned());
+            span.record(&mut Visitor(&mut fields));
+            let mut spans = self.0.lock().unwrap();
+            spans.push(fields);
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.0.lock().unwrap();
+            let index = usize::try_from(span.into_u64()).unwrap() - 1;
+            values.record(&mut Visitor(&mut spans[index]));
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    fn success(metadata: Vec<(&str, BoltType)>) -> Result<BoltResponse> {
+        let metadata = BoltMap::from_entries(metadata);
+        Ok(BoltResponse::Success(Success::new(metadata)))
+    }
+
+    #[test]
+    fn should_record_a_span_per_request() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let server = BoltServer::parse("localhost:7687").unwrap();
+            let mut spans = RequestSpans::new(server, true);
+            spans.negotiated(Version::V5_4);
+
+            spans.sent(&BoltRequest::begin(
+                Some("movies"),
+                &[],
+                &TxConfig::default(),
+                Version::V5_4,
+            ));
+            spans.received(&success(vec![]));
+            spans.sent(&BoltRequest::run(
+                None,
+                "MATCH (n) RETURN n",
+                Default::default(),
+            ));
+            spans.sent(&BoltRequest::pull(1000, 0));
+            spans.received(&success(vec![
+                ("fields", BoltType::List(BoltList::new())),
+                ("qid", 0.into()),
+            ]));
+            let record = Bytes::from_static(&[0xB1, 0x71, 0x90]);
+            spans.received(&BoltResponse::parse(Version::V5_4, record));
+            spans.received(&success(vec![("has_more", false.into())]));
+            spans.sent(&BoltRequest::commit());
+            spans.received(&Err(Error::ReadTimeout));
+        });
+
+        let spans = recorder.0.lock().unwrap();
+        let [begin, run, pull, commit] = &spans[..] else {
+            panic!("expected 4 spans, got {spans:?}");
+        };
+        for span in [begin, run, pull, commit] {
+            assert_eq!(span["name"], "neo4j.request");
+            assert_eq!(span["db.system"], "neo4j");
+            assert_eq!(span["db.namespace"], "movies");
+            assert_eq!(span["server.address"], "localhost");
+            assert_eq!(span["server.port"], "7687");
+            assert_eq!(span["network.protocol.version"], "5.4");
+        }
+        assert_eq!(begin["otel.name"], "BEGIN movies");
+        assert_eq!(run["db.operation.name"], "RUN");
+        assert_eq!(run["db.query.text"], "MATCH (n) RETURN n");
+        assert_eq!(run["neo4j.qid"], "0");
+        assert_eq!(pull["neo4j.qid"], "0");
+        assert_eq!(pull["db.response.returned_rows"], "1");
+        assert!(!begin.contains_key("error.type"));
+        assert_eq!(commit["error.type"], "ReadTimeout");
+        assert_eq!(commit["otel.status_code"], "ERROR");
+    }
+
+    #[test]
+    fn should_only_record_the_query_text_on_request() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let server = BoltServer::parse("localhost:7687").unwrap();
+            let mut spans = RequestSpans::new(server, false);
+            spans.sent(&BoltRequest::run(None, "RETURN 1", Default::default()));
+        });
+
+        let spans = recorder.0.lock().unwrap();
+        assert!(!spans[0].contains_key("db.query.text"));
+        assert!(!spans[0].contains_key("db.namespace"));
+    }
+}
@@ -0,0 +1,470 @@
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use log::warn;
+use tokio_rustls::rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{
+        hash::{Hash, HashAlgorithm},
+        verify_tls12_signature, verify_tls13_signature, CryptoProvider,
+    },
+    pki_types::{CertificateDer, ServerName, UnixTime},
+    version::{TLS12, TLS13},
+    ClientConfig, ConfigBuilder, DigitallySignedStruct, Error as TlsError, InconsistentKeys,
+    RootCertStore, SignatureScheme, SupportedCipherSuite, SupportedProtocolVersion, WantsVerifier,
+};
+
+use crate::{
+    auth::ClientIdentity,
+    errors::{Error, Result},
+};
+
+/// The TLS settings for the encrypted URI schemes `bolt+s`, `neo4j+s`,
+/// `bolt+ssc` and `neo4j+ssc`, see [`crate::ConfigBuilder::with_tls_config`].
+///
+/// By default, the certificate of the server is verified against the native root
+/// certificates of the platform, with either TLS 1.2 or TLS 1.3.
+///
+/// ```
+/// use neo4rs::{TlsConfig, TlsVersion};
+///
+/// let tls = TlsConfig::new()
+///     .without_native_roots()
+///     .with_ca_certificates_pem(b"-----BEGIN CERTIFICATE-----...".to_vec())
+///     .with_min_version(TlsVersion::Tls13)
+///     .with_server_name("neo4j.internal.example.com");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    native_roots: bool,
+    ca_certificates: Vec<CaCertificates>,
+    client_identity: Option<ClientIdentity>,
+    fingerprint: Option<[u8; 32]>,
+    min_version: TlsVersion,
+    server_name: Option<String>,
+    client_config: Option<Arc<ClientConfig>>,
+}
+
+/// The lowest TLS version that is accepted, see [`TlsConfig::with_min_version`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsVersion {
+    #[default]
+    Tls12,
+    Tls13,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CaCertificates {
+    File(PathBuf),
+    Pem(Arc<[u8]>),
+}
+
+impl TlsConfig {
+    /// The default settings, which verify the server against the native root certificates
+    /// and accept TLS 1.2 or later.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Does not trust the native root certificates of the platform,
+    /// only the CA certificates added to this config.
+    pub fn without_native_roots(mut self) -> Self {
+        self.native_roots = false;
+        self
+    }
+
+    /// Trusts the CA certificates in the given PEM file, in addition to the native roots.
+    pub fn with_ca_certificates_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.ca_certificates
+            .push(CaCertificates::File(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Trusts the CA certificates in the given PEM bundle, in addition to the native roots.
+    pub fn with_ca_certificates_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_certificates
+            .push(CaCertificates::Pem(pem.into().into()));
+        self
+    }
+
+    /// The certificate chain and private key that are presented to the server for mutual TLS.
+    pub fn with_client_identity(mut self, identity: ClientIdentity) -> Self {
+        self.client_identity = Some(identity);
+        self
+    }
+
+    /// Pins the certificate of the server to the given SHA-256 fingerprint of its DER encoding,
+    /// as printed by `openssl x509 -in cert.pem -outform der | sha256sum`.
+    ///
+    /// Only applies to the `bolt+ssc://` and `neo4j+ssc://` schemes, which otherwise accept
+    /// any certificate, including self-signed ones.
+    pub fn with_certificate_fingerprint(mut self, sha256: [u8; 32]) -> Self {
+        self.fingerprint = Some(sha256);
+        self
+    }
+
+    /// Refuses to connect with a TLS version older than the given one.
+    pub fn with_min_version(mut self, version: TlsVersion) -> Self {
+        self.min_version = version;
+        self
+    }
+
+    /// The name that is sent as SNI and that the certificate of the server is verified against,
+    /// instead of the host of the URI, e.g. when connecting through a tunnel or by IP address.
+    pub fn with_server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.server_name = Some(server_name.into());
+        self
+    }
+
+    /// Uses the given `rustls` config as is, for full control over the TLS connection.
+    /// All other settings except for [`TlsConfig::with_server_name`] are ignored.
+    pub fn with_client_config(mut self, config: Arc<ClientConfig>) -> Self {
+        self.client_config = Some(config);
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn ca_certificates(&self) -> &[CaCertificates] {
+        &self.ca_certificates
+    }
+
+    /// The name the certificate of the server is verified against, if it is overridden.
+    pub(crate) fn server_name(&self) -> Result<Option<ServerName<'static>>> {
+        self.server_name
+            .as_ref()
+            .map(|name| {
+                ServerName::try_from(name.clone()).map_err(|_| Error::InvalidDnsName(name.clone()))
+            })
+            .transpose()
+    }
+
+    /// The config for the `+s` schemes, which verify the certificate chain of the server
+    /// against the native roots and the configured CA certificates.
+    pub(crate) fn client_config(&self) -> Result<Arc<ClientConfig>> {
+        if let Some(config) = &self.client_config {
+            return Ok(config.clone());
+        }
+        if self.fingerprint.is_some() {
+            warn!("The certificate fingerprint is ignored, it only applies to the `+ssc` schemes.");
+        }
+
+        let mut root_cert_store = RootCertStore::empty();
+        if self.native_roots {
+            match rustls_native_certs::load_native_certs() {
+                Ok(certs) => {
+                    root_cert_store.add_parsable_certificates(certs);
+                }
+                Err(e) => {
+                    warn!("Failed to load native certificates: {e}");
+                }
+            }
+        }
+
+        for certificates in &self.ca_certificates {
+            let certs: Vec<_> = match certificates {
+                CaCertificates::File(path) => {
+                    let mut reader = BufReader::new(File::open(path)?);
+                    rustls_pemfile::certs(&mut reader).flatten().collect()
+                }
+                CaCertificates::Pem(pem) => rustls_pemfile::certs(&mut &**pem).flatten().collect(),
+            };
+            root_cert_store.add_parsable_certificates(certs);
+        }
+
+        if root_cert_store.is_empty() {
+            warn!("No trusted root certificates are configured, the server cannot be verified.");
+        }
+
+        let builder = self.builder().with_root_certificates(root_cert_store);
+        let config = match &self.client_identity {
+            Some(identity) => builder
+                .with_client_auth_cert(identity.cert_chain()?, identity.private_key()?)
+                .map_err(invalid_identity)?,
+            None => builder.with_no_client_auth(),
+        };
+        Ok(Arc::new(config))
+    }
+
+    /// The config for the `+ssc` schemes, which accept self-signed certificates.
+    pub(crate) fn self_signed_client_config(&self) -> Result<Arc<ClientConfig>> {
+        if let Some(config) = &self.client_config {
+            return Ok(config.clone());
+        }
+
+        let builder = self.builder();
+        let verifier = SelfSignedVerifier {
+            provider: builder.crypto_provider().clone(),
+            fingerprint: self.fingerprint,
+        };
+        let builder = builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier));
+        let config = match &self.client_identity {
+            Some(identity) => builder
+                .with_client_auth_cert(identity.cert_chain()?, identity.private_key()?)
+                .map_err(invalid_identity)?,
+            None => builder.with_no_client_auth(),
+        };
+        Ok(Arc::new(config))
+    }
+
+    fn builder(&self) -> ConfigBuilder<ClientConfig, WantsVerifier> {
+        let versions: &[&'static SupportedProtocolVersion] = match self.min_version {
+            TlsVersion::Tls12 => &[&TLS13, &TLS12],
+            TlsVersion::Tls13 => &[&TLS13],
+        };
+        ClientConfig::builder_with_protocol_versions(versions)
+    }
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            native_roots: true,
+            ca_certificates: Vec::new(),
+            client_identity: None,
+            fingerprint: None,
+            min_version: TlsVersion::default(),
+            server_name: None,
+            client_config: None,
+        }
+    }
+}
+
+fn invalid_identity(error: TlsError) -> Error {
+    Error::InvalidClientIdentity(match error {
+        TlsError::InconsistentKeys(InconsistentKeys::KeyMismatch) => {
+            "the private key does not match the certificate".to_owned()
+        }
+        e => format!("the private key cannot be used: {e}"),
+    })
+}
+
+/// Accepts any certificate of the server, including a self-signed one,
+/// as long as the server proves that it owns the private key of the certificate.
+/// With a pinned fingerprint, only the certificate with that SHA-256 fingerprint is accepted.
+#[derive(Debug)]
+struct SelfSignedVerifier {
+    provider: Arc<CryptoProvider>,
+    fingerprint: Option<[u8; 32]>,
+}
+
+impl SelfSignedVerifier {
+    fn sha256(&self) -> Option<&'static dyn Hash> {
+        self.provider
+            .cipher_suites
+            .iter()
+            .map(|suite| match suite {
+                SupportedCipherSuite::Tls12(suite) => suite.common.hash_provider,
+                SupportedCipherSuite::Tls13(suite) => suite.common.hash_provider,
+            })
+            .find(|hash| hash.algorithm() == HashAlgorithm::SHA256)
+    }
+}
+
+impl ServerCertVerifier for SelfSignedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        if let Some(expected) = &self.fingerprint {
+            let sha256 = self.sha256().ok_or_else(|| {
+                TlsError::General("no SHA-256 implementation to check the fingerprint".into())
+            })?;
+            if sha256.hash(end_entity).as_ref() != expected {
+                return Err(TlsError::General(
+                    "the certificate of the server does not match the pinned fingerprint".into(),
+                ));
+            }
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"))
+    }
+
+    fn with_identity(identity: ClientIdentity) -> TlsConfig {
+        TlsConfig::new().with_client_identity(identity)
+    }
+
+    #[test]
+    fn should_load_client_identity() {
+        for (cert, key) in [
+            ("client-ec.pem", "client-ec.key"),
+            ("client-ec.pem", "client-ec-pkcs8.key"),
+            ("client-rsa.pem", "client-rsa.key"),
+        ] {
+            let options = with_identity(ClientIdentity::from_files(fixture(cert), fixture(key)));
+            assert!(options.client_config().is_ok(), "{key} should load");
+            assert!(
+                options.self_signed_client_config().is_ok(),
+                "{key} should load"
+            );
+        }
+
+        let pem = |name| std::fs::read(fixture(name)).unwrap();
+        let identity = ClientIdentity::from_pem(pem("client-ec.pem"), pem("client-ec.key"));
+        assert!(with_identity(identity).client_config().is_ok());
+    }
+
+    #[test]
+    fn should_reject_invalid_client_identity() {
+        let error = |identity: ClientIdentity| match with_identity(identity).client_config() {
+            Err(Error::InvalidClientIdentity(message)) => message,
+            otherwise => panic!("expected an invalid client identity, got {otherwise:?}"),
+        };
+
+        let message = error(ClientIdentity::from_files(
+            fixture("client-ec.pem"),
+            fixture("client-rsa.key"),
+        ));
+        assert!(message.contains("does not match"), "{message}");
+
+        let message = error(ClientIdentity::from_files(
+            fixture("client-ec.pem"),
+            fixture("missing.key"),
+        ));
+        assert!(message.contains("cannot read private key"), "{message}");
+
+        let message = error(ClientIdentity::from_files(
+            fixture("client-ec.pem"),
+            fixture("client-ec.pem"),
+        ));
+        assert!(
+            message.contains("no PKCS#8, RSA or EC private key"),
+            "{message}"
+        );
+
+        let message = error(ClientIdentity::from_pem(b"".to_vec(), b"".to_vec()));
+        assert!(message.contains("no certificate found"), "{message}");
+    }
+
+    #[test]
+    fn should_accept_self_signed_certificates() {
+        let pem = std::fs::read(fixture("client-ec.pem")).unwrap();
+        let cert = rustls_pemfile::certs(&mut &*pem).next().unwrap().unwrap();
+        let verify = |fingerprint| {
+            let verifier = SelfSignedVerifier {
+                provider: ClientConfig::builder().crypto_provider().clone(),
+                fingerprint,
+            };
+            let server_name = ServerName::try_from("localhost").unwrap();
+            verifier.verify_server_cert(&cert, &[], &server_name, &[], UnixTime::now())
+        };
+
+        assert!(verify(None).is_ok());
+
+        // openssl x509 -in client-ec.pem -outform der | sha256sum
+        let fingerprint = "622ac7fc371f004bd793970e2a613f401825dcc8d4d623d67f625cea4ede2854";
+        let mut pinned = [0; 32];
+        for (i, byte) in pinned.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&fingerprint[2 * i..2 * i + 2], 16).unwrap();
+        }
+        assert!(verify(Some(pinned)).is_ok());
+
+        pinned[0] ^= 0xFF;
+        assert!(verify(Some(pinned)).is_err());
+    }
+
+    #[test]
+    fn should_pin_min_version() {
+        let tls = TlsConfig::new().with_min_version(TlsVersion::Tls13);
+        assert!(tls.client_config().is_ok());
+        assert!(tls.self_signed_client_config().is_ok());
+        assert!(TlsVersion::Tls12 < TlsVersion::Tls13);
+    }
+
+    #[test]
+    fn should_load_ca_certificates_from_pem() {
+        let pem = std::fs::read(fixture("client-ec.pem")).unwrap();
+        let tls = TlsConfig::new()
+            .without_native_roots()
+            .with_ca_certificates_pem(pem)
+            .with_ca_certificates_file(fixture("client-rsa.pem"));
+        assert_eq!(tls.ca_certificates().len(), 2);
+        assert!(tls.client_config().is_ok());
+
+        let tls = TlsConfig::new().with_ca_certificates_file(fixture("missing.pem"));
+        assert!(matches!(tls.client_config(), Err(Error::IOError { .. })));
+    }
+
+    #[test]
+    fn should_override_server_name() {
+        assert_eq!(TlsConfig::new().server_name().unwrap(), None);
+        assert_eq!(
+            TlsConfig::new()
+                .with_server_name("neo4j.example.com")
+                .server_name()
+                .unwrap(),
+            Some(ServerName::try_from("neo4j.example.com").unwrap())
+        );
+        assert!(matches!(
+            TlsConfig::new().with_server_name("not a name").server_name(),
+            Err(Error::InvalidDnsName(name)) if name == "not a name"
+        ));
+    }
+
+    #[test]
+    fn should_use_custom_client_config_as_is() {
+        let custom = TlsConfig::new()
+            .without_native_roots()
+            .client_config()
+            .unwrap();
+        let tls = TlsConfig::new()
+            .with_certificate_fingerprint([0; 32])
+            .with_client_config(custom.clone());
+        assert!(Arc::ptr_eq(&tls.client_config().unwrap(), &custom));
+        assert!(Arc::ptr_eq(
+            &tls.self_signed_client_config().unwrap(),
+            &custom
+        ));
+    }
+}
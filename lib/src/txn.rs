@@ -1,9 +1,11 @@
 use crate::{
-    config::Database,
+    bookmarks::Bookmarks,
+    config::{Database, TxConfig},
     errors::Result,
     messages::{BoltRequest, BoltResponse},
     pool::ManagedConnection,
     query::Query,
+    result_summary::ResultSummary,
     stream::RowStream,
 };
 
@@ -12,25 +14,37 @@ use crate::{
 /// When a transation is started, a dedicated connection is resered and moved into the handle which
 /// will be released to the connection pool when the [`Txn`] handle is dropped.
 pub struct Txn {
-    db: Database,
+    db: Option<Database>,
     fetch_size: usize,
     connection: ManagedConnection,
+    bookmarks: Bookmarks,
 }
 
 impl Txn {
     pub(crate) async fn new(
-        db: Database,
+        db: Option<Database>,
         fetch_size: usize,
+        bookmarks: Bookmarks,
+        config: &TxConfig,
         mut connection: ManagedConnection,
     ) -> Result<Self> {
-        let begin = BoltRequest::begin(&db);
+        config.check_version(connection.version())?;
+        let begin = BoltRequest::begin(
+            db.as_deref(),
+            bookmarks.used(),
+            config,
+            connection.version(),
+        );
         match connection.send_recv(begin).await? {
             BoltResponse::Success(_) => Ok(Txn {
                 db,
                 fetch_size,
                 connection,
+                bookmarks,
             }),
-            msg => Err(msg.into_error("BEGIN")),
+            msg => Err(msg
+                .into_error("BEGIN")
+                .for_impersonation(config.imp_user.as_deref())),
         }
     }
 
@@ -45,14 +59,14 @@ impl Txn {
         Ok(())
     }
 
-    /// Runs a single query and discards the stream.
-    pub async fn run(&mut self, q: Query) -> Result<()> {
-        q.run(&self.db, &mut self.connection).await
+    /// Runs a single query, discards the stream and returns the summary of the query.
+    pub async fn run(&mut self, q: Query) -> Result<ResultSummary> {
+        q.run(self.db.as_deref(), &mut self.connection).await
     }
 
     /// Executes a query and returns a [`RowStream`]
     pub async fn execute(&mut self, q: Query) -> Result<RowStream> {
-        q.execute_mut(&self.db, self.fetch_size, &mut self.connection)
+        q.execute_mut(self.db.as_deref(), self.fetch_size, &mut self.connection)
             .await
     }
 
@@ -60,7 +74,10 @@ impl Txn {
     pub async fn commit(mut self) -> Result<()> {
         let commit = BoltRequest::commit();
         match self.connection.send_recv(commit).await? {
-            BoltResponse::Success(_) => Ok(()),
+            BoltResponse::Success(success) => {
+                self.bookmarks.update(&success);
+                Ok(())
+            }
             msg => Err(msg.into_error("COMMIT")),
         }
     }
@@ -2,7 +2,7 @@ use crate::{
     errors::Error,
     types::{BoltInteger, Result},
 };
-use chrono::{Days, NaiveDate, NaiveDateTime};
+use chrono::{DateTime, Days, NaiveDate};
 use neo4rs_macros::BoltStruct;
 use std::convert::TryInto;
 
@@ -32,11 +32,11 @@ impl TryFrom<&BoltDate> for NaiveDate {
     fn try_from(value: &BoltDate) -> Result<Self> {
         let days = Days::new(value.days.value.unsigned_abs());
         if value.days.value >= 0 {
-            NaiveDateTime::UNIX_EPOCH.checked_add_days(days)
+            DateTime::UNIX_EPOCH.date_naive().checked_add_days(days)
         } else {
-            NaiveDateTime::UNIX_EPOCH.checked_sub_days(days)
+            DateTime::UNIX_EPOCH.date_naive().checked_sub_days(days)
         }
-        .map_or(Err(Error::ConversionError), |o| Ok(o.date()))
+        .ok_or(Error::ConversionError)
     }
 }
 
@@ -1,11 +1,21 @@
 use crate::errors::Error;
 use crate::types::*;
-use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, Timelike};
+use crate::version::Version;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone, Timelike};
 use neo4rs_macros::BoltStruct;
 use std::convert::TryInto;
 
-#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
-#[signature(0xB3, 0x46)]
+/// Before Bolt 5.0, the seconds of a datetime were the local time of its offset or zone.
+const LEGACY_DATE_TIME: u8 = 0x46;
+const LEGACY_DATE_TIME_ZONE_ID: u8 = 0x66;
+/// Since Bolt 5.0, the seconds of a datetime are UTC.
+const DATE_TIME: u8 = 0x49;
+const DATE_TIME_ZONE_ID: u8 = 0x69;
+
+/// The seconds are always stored in the local time of the offset,
+/// the UTC encoding of Bolt 5.0 is converted when reading and writing.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoltDateTime {
     pub(crate) seconds: BoltInteger,
     pub(crate) nanoseconds: BoltInteger,
@@ -19,12 +29,97 @@ pub struct BoltLocalDateTime {
     pub(crate) nanoseconds: BoltInteger,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
-#[signature(0xB3, 0x66)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoltDateTimeZoneId {
     pub(crate) seconds: BoltInteger,
     pub(crate) nanoseconds: BoltInteger,
     pub(crate) tz_id: BoltString,
+    /// Whether `seconds` are UTC (Bolt 5.0+) or the local time of the zone.
+    /// Converting local times is ambiguous around DST transitions, so the
+    /// encoding that was received is kept as is.
+    pub(crate) utc: bool,
+}
+
+impl BoltWireFormat for BoltDateTime {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && input[0] == 0xB3 && matches!(input[1], LEGACY_DATE_TIME | DATE_TIME)
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        input.get_u8();
+        let tag = input.get_u8();
+        let seconds = BoltInteger::parse(version, input)?;
+        let nanoseconds = BoltInteger::parse(version, input)?;
+        let tz_offset_seconds = BoltInteger::parse(version, input)?;
+        let seconds = if tag == DATE_TIME {
+            seconds
+                .value
+                .checked_add(tz_offset_seconds.value)
+                .ok_or(Error::ConversionError)?
+                .into()
+        } else {
+            seconds
+        };
+        Ok(BoltDateTime {
+            seconds,
+            nanoseconds,
+            tz_offset_seconds,
+        })
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        let (tag, seconds) = if version >= Version::V5_0 {
+            let seconds = self
+                .seconds
+                .value
+                .checked_sub(self.tz_offset_seconds.value)
+                .ok_or(Error::ConversionError)?;
+            (DATE_TIME, seconds)
+        } else {
+            (LEGACY_DATE_TIME, self.seconds.value)
+        };
+        bytes.reserve(2);
+        bytes.put_u8(0xB3);
+        bytes.put_u8(tag);
+        BoltInteger::new(seconds).write_into(version, bytes)?;
+        self.nanoseconds.write_into(version, bytes)?;
+        self.tz_offset_seconds.write_into(version, bytes)?;
+        Ok(())
+    }
+}
+
+impl BoltWireFormat for BoltDateTimeZoneId {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2
+            && input[0] == 0xB3
+            && matches!(input[1], LEGACY_DATE_TIME_ZONE_ID | DATE_TIME_ZONE_ID)
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        input.get_u8();
+        let tag = input.get_u8();
+        Ok(BoltDateTimeZoneId {
+            seconds: BoltInteger::parse(version, input)?,
+            nanoseconds: BoltInteger::parse(version, input)?,
+            tz_id: BoltString::parse(version, input)?,
+            utc: tag == DATE_TIME_ZONE_ID,
+        })
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        let (tag, seconds) = if version >= Version::V5_0 {
+            (DATE_TIME_ZONE_ID, self.utc_seconds()?)
+        } else {
+            (LEGACY_DATE_TIME_ZONE_ID, self.local_seconds()?)
+        };
+        bytes.reserve(2);
+        bytes.put_u8(0xB3);
+        bytes.put_u8(tag);
+        BoltInteger::new(seconds).write_into(version, bytes)?;
+        self.nanoseconds.write_into(version, bytes)?;
+        self.tz_id.write_into(version, bytes)?;
+        Ok(())
+    }
 }
 
 impl BoltDateTime {
@@ -47,6 +142,38 @@ impl BoltDateTimeZoneId {
     pub fn tz_id(&self) -> &str {
         &self.tz_id.value
     }
+
+    fn tz(&self) -> Result<chrono_tz::Tz> {
+        self.tz_id.value.parse().map_err(|_| Error::ConversionError)
+    }
+
+    fn naive(&self) -> Result<NaiveDateTime> {
+        DateTime::from_timestamp(self.seconds.value, self.nanoseconds.value as u32)
+            .map(|datetime| datetime.naive_utc())
+            .ok_or(Error::ConversionError)
+    }
+
+    /// The seconds since the epoch in the local time of the zone.
+    pub(crate) fn local_seconds(&self) -> Result<i64> {
+        if !self.utc {
+            return Ok(self.seconds.value);
+        }
+        let local = self.tz()?.from_utc_datetime(&self.naive()?).naive_local();
+        Ok(local.and_utc().timestamp())
+    }
+
+    /// The seconds since the epoch in UTC.
+    /// Ambiguous local times resolve to the earlier instant.
+    pub(crate) fn utc_seconds(&self) -> Result<i64> {
+        if self.utc {
+            return Ok(self.seconds.value);
+        }
+        self.tz()?
+            .from_local_datetime(&self.naive()?)
+            .earliest()
+            .map(|datetime| datetime.timestamp())
+            .ok_or(Error::ConversionError)
+    }
 }
 
 impl From<(NaiveDateTime, &str)> for BoltDateTimeZoneId {
@@ -57,6 +184,7 @@ impl From<(NaiveDateTime, &str)> for BoltDateTimeZoneId {
             seconds,
             nanoseconds,
             tz_id: value.1.into(),
+            utc: false,
         }
     }
 }
@@ -65,9 +193,8 @@ impl TryInto<(NaiveDateTime, String)> for BoltDateTimeZoneId {
     type Error = Error;
 
     fn try_into(self) -> Result<(NaiveDateTime, String)> {
-        DateTime::from_timestamp(self.seconds.value, self.nanoseconds.value as u32)
-            .map(|datetime| (datetime.naive_utc(), self.tz_id.into()))
-            .ok_or(Error::ConversionError)
+        let datetime = NaiveDateTime::try_from(&self)?;
+        Ok((datetime, self.tz_id.into()))
     }
 }
 
@@ -75,7 +202,7 @@ impl TryFrom<&BoltDateTimeZoneId> for NaiveDateTime {
     type Error = Error;
 
     fn try_from(value: &BoltDateTimeZoneId) -> Result<Self, Self::Error> {
-        DateTime::from_timestamp(value.seconds.value, value.nanoseconds.value as u32)
+        DateTime::from_timestamp(value.local_seconds()?, value.nanoseconds.value as u32)
             .map(|datetime| datetime.naive_utc())
             .ok_or(Error::ConversionError)
     }
@@ -85,19 +212,15 @@ impl TryFrom<&BoltDateTimeZoneId> for DateTime<FixedOffset> {
     type Error = Error;
 
     fn try_from(value: &BoltDateTimeZoneId) -> std::result::Result<Self, Self::Error> {
-        let tz: chrono_tz::Tz = value
-            .tz_id
-            .value
-            .parse()
-            .map_err(|_| Error::ConversionError)?;
-
-        let seconds = value.seconds.value;
-        let nanoseconds = value.nanoseconds.value as u32;
-
-        let dt = DateTime::from_timestamp(seconds, nanoseconds).ok_or(Error::ConversionError)?;
-        let dt = chrono::TimeZone::from_local_datetime(&tz, &dt.naive_utc())
-            .single()
-            .ok_or(Error::ConversionError)?;
+        let tz = value.tz()?;
+        let dt = value.naive()?;
+        let dt = if value.utc {
+            tz.from_utc_datetime(&dt)
+        } else {
+            tz.from_local_datetime(&dt)
+                .single()
+                .ok_or(Error::ConversionError)?
+        };
         Ok(dt.fixed_offset())
     }
 }
@@ -269,4 +392,86 @@ mod tests {
         assert_eq!(datetime.to_string(), "2015-07-01 08:59:60.123");
         assert_eq!(zone_id, "Europe/Paris");
     }
+
+    #[test]
+    fn should_serialize_a_utc_datetime() {
+        let date: BoltDateTime = DateTime::parse_from_rfc2822("Wed, 24 Jun 2015 12:50:35 +0100")
+            .unwrap()
+            .into();
+
+        assert_eq!(
+            date.into_bytes(Version::V5_0).unwrap(),
+            Bytes::from_static(&[
+                0xB3, 0x49, 0xCA, 0x55, 0x8A, 0x99, 0x8B, 0x00, 0xC9, 0x0E, 0x10,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_utc_datetime() {
+        let mut bytes = Bytes::from_static(&[
+            0xB3, 0x49, 0xCA, 0x55, 0x8A, 0x99, 0x8B, 0x00, 0xC9, 0x0E, 0x10,
+        ]);
+
+        let datetime = BoltDateTime::parse(Version::V5_0, &mut bytes).unwrap();
+        let expected: BoltDateTime =
+            DateTime::parse_from_rfc2822("Wed, 24 Jun 2015 12:50:35 +0100")
+                .unwrap()
+                .into();
+        assert_eq!(datetime, expected);
+
+        let datetime: DateTime<FixedOffset> = datetime.try_into().unwrap();
+        assert_eq!(datetime.to_rfc2822(), "Wed, 24 Jun 2015 12:50:35 +0100");
+    }
+
+    #[test]
+    fn should_serialize_a_utc_datetime_with_zoneid() {
+        let datetime =
+            NaiveDateTime::parse_from_str("2015-07-01 08:59:60.123", "%Y-%m-%d %H:%M:%S%.f")
+                .unwrap();
+
+        let date: BoltDateTimeZoneId = (datetime, "Europe/Paris").into();
+
+        // 06:59:60 UTC, Paris is at +02:00 in summer
+        assert_eq!(
+            date.into_bytes(Version::V5_0).unwrap(),
+            Bytes::from_static(&[
+                0xB3, 0x69, 0xCA, 0x55, 0x93, 0x8F, 0xEF, 0xCA, 0x42, 0xEF, 0x9E, 0xC0, 0x8C, 0x45,
+                0x75, 0x72, 0x6F, 0x70, 0x65, 0x2F, 0x50, 0x61, 0x72, 0x69, 0x73,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_utc_datetime_with_zoneid() {
+        let mut bytes = Bytes::from_static(&[
+            0xB3, 0x69, 0xCA, 0x55, 0x93, 0x8F, 0xEF, 0xCA, 0x42, 0xEF, 0x9E, 0xC0, 0x8C, 0x45,
+            0x75, 0x72, 0x6F, 0x70, 0x65, 0x2F, 0x50, 0x61, 0x72, 0x69, 0x73,
+        ]);
+
+        let value = BoltDateTimeZoneId::parse(Version::V5_0, &mut bytes).unwrap();
+        let datetime: DateTime<FixedOffset> = value.try_to_chrono().unwrap();
+        assert_eq!(datetime.to_rfc3339(), "2015-07-01T08:59:60.123+02:00");
+
+        let (datetime, zone_id) = value.try_into().unwrap();
+        assert_eq!(datetime.to_string(), "2015-07-01 08:59:60.123");
+        assert_eq!(zone_id, "Europe/Paris");
+    }
+
+    #[test]
+    fn should_convert_between_datetime_encodings() {
+        let mut bytes = Bytes::from_static(&[
+            0xB3, 0x69, 0xCA, 0x55, 0x93, 0x8F, 0xEF, 0xCA, 0x42, 0xEF, 0x9E, 0xC0, 0x8C, 0x45,
+            0x75, 0x72, 0x6F, 0x70, 0x65, 0x2F, 0x50, 0x61, 0x72, 0x69, 0x73,
+        ]);
+
+        let value = BoltDateTimeZoneId::parse(Version::V5_0, &mut bytes).unwrap();
+        assert_eq!(
+            value.into_bytes(Version::V4_4).unwrap(),
+            Bytes::from_static(&[
+                0xB3, 0x66, 0xCA, 0x55, 0x93, 0xAC, 0x0F, 0xCA, 0x42, 0xEF, 0x9E, 0xC0, 0x8C, 0x45,
+                0x75, 0x72, 0x6F, 0x70, 0x65, 0x2F, 0x50, 0x61, 0x72, 0x69, 0x73,
+            ])
+        );
+    }
 }
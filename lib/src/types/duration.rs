@@ -4,10 +4,10 @@ use neo4rs_macros::BoltStruct;
 #[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
 #[signature(0xB4, 0x45)]
 pub struct BoltDuration {
-    months: BoltInteger,
-    days: BoltInteger,
-    seconds: BoltInteger,
-    nanoseconds: BoltInteger,
+    pub(crate) months: BoltInteger,
+    pub(crate) days: BoltInteger,
+    pub(crate) seconds: BoltInteger,
+    pub(crate) nanoseconds: BoltInteger,
 }
 
 impl BoltDuration {
@@ -67,6 +67,14 @@ impl FromIterator<(BoltString, BoltType)> for BoltMap {
     }
 }
 
+#[cfg(test)]
+impl BoltMap {
+    /// Builds a map with string keys, e.g. as a fixture for the metadata of a response.
+    pub(crate) fn from_entries<'a>(entries: impl IntoIterator<Item = (&'a str, BoltType)>) -> Self {
+        entries.into_iter().map(|(k, v)| (k.into(), v)).collect()
+    }
+}
+
 impl BoltWireFormat for BoltMap {
     fn can_parse(_version: Version, input: &[u8]) -> bool {
         let marker = input[0];
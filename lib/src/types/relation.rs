@@ -1,23 +1,59 @@
-use crate::types::{serde::DeError, BoltInteger, BoltMap, BoltString, BoltType, Result};
+use crate::{
+    types::{
+        node::element_id_or_id, serde::DeError, BoltInteger, BoltMap, BoltString, BoltType,
+        BoltWireFormat, Result,
+    },
+    Version,
+};
 use ::serde::Deserialize;
-use neo4rs_macros::BoltStruct;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB5, 0x52)]
+const RELATION: u8 = 0x52;
+const UNBOUNDED_RELATION: u8 = 0x72;
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltRelation {
     pub id: BoltInteger,
     pub start_node_id: BoltInteger,
     pub end_node_id: BoltInteger,
     pub typ: BoltString,
     pub properties: BoltMap,
+    /// Only sent by servers since Bolt 5.0.
+    pub element_id: Option<BoltString>,
+    /// Only sent by servers since Bolt 5.0.
+    pub start_node_element_id: Option<BoltString>,
+    /// Only sent by servers since Bolt 5.0.
+    pub end_node_element_id: Option<BoltString>,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB3, 0x72)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltUnboundedRelation {
     pub id: BoltInteger,
     pub typ: BoltString,
     pub properties: BoltMap,
+    /// Only sent by servers since Bolt 5.0.
+    pub element_id: Option<BoltString>,
+}
+
+impl BoltRelation {
+    pub fn new(
+        id: BoltInteger,
+        start_node_id: BoltInteger,
+        end_node_id: BoltInteger,
+        typ: BoltString,
+        properties: BoltMap,
+    ) -> Self {
+        BoltRelation {
+            id,
+            start_node_id,
+            end_node_id,
+            typ,
+            properties,
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
+        }
+    }
 }
 
 impl BoltUnboundedRelation {
@@ -26,7 +62,86 @@ impl BoltUnboundedRelation {
             id,
             typ,
             properties,
+            element_id: None,
+        }
+    }
+}
+
+impl BoltWireFormat for BoltRelation {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && matches!(input[0], 0xB5 | 0xB8) && input[1] == RELATION
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let marker = input.get_u8();
+        input.get_u8();
+        let mut relation = BoltRelation::new(
+            BoltInteger::parse(version, input)?,
+            BoltInteger::parse(version, input)?,
+            BoltInteger::parse(version, input)?,
+            BoltString::parse(version, input)?,
+            BoltMap::parse(version, input)?,
+        );
+        if marker == 0xB8 {
+            relation.element_id = Some(BoltString::parse(version, input)?);
+            relation.start_node_element_id = Some(BoltString::parse(version, input)?);
+            relation.end_node_element_id = Some(BoltString::parse(version, input)?);
+        }
+        Ok(relation)
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 { 0xB8 } else { 0xB5 });
+        bytes.put_u8(RELATION);
+        self.id.write_into(version, bytes)?;
+        self.start_node_id.write_into(version, bytes)?;
+        self.end_node_id.write_into(version, bytes)?;
+        self.typ.write_into(version, bytes)?;
+        self.properties.write_into(version, bytes)?;
+        if v5 {
+            element_id_or_id(&self.element_id, &self.id).write_into(version, bytes)?;
+            element_id_or_id(&self.start_node_element_id, &self.start_node_id)
+                .write_into(version, bytes)?;
+            element_id_or_id(&self.end_node_element_id, &self.end_node_id)
+                .write_into(version, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+impl BoltWireFormat for BoltUnboundedRelation {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && matches!(input[0], 0xB3 | 0xB4) && input[1] == UNBOUNDED_RELATION
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let marker = input.get_u8();
+        input.get_u8();
+        let mut relation = BoltUnboundedRelation::new(
+            BoltInteger::parse(version, input)?,
+            BoltString::parse(version, input)?,
+            BoltMap::parse(version, input)?,
+        );
+        if marker == 0xB4 {
+            relation.element_id = Some(BoltString::parse(version, input)?);
         }
+        Ok(relation)
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 { 0xB4 } else { 0xB3 });
+        bytes.put_u8(UNBOUNDED_RELATION);
+        self.id.write_into(version, bytes)?;
+        self.typ.write_into(version, bytes)?;
+        self.properties.write_into(version, bytes)?;
+        if v5 {
+            element_id_or_id(&self.element_id, &self.id).write_into(version, bytes)?;
+        }
+        Ok(())
     }
 }
 
@@ -74,13 +189,7 @@ mod tests {
         let typ = BoltString::new("rel");
         let properties = vec![("name".into(), "Mark".into())].into_iter().collect();
 
-        let relation = BoltRelation {
-            id,
-            start_node_id,
-            end_node_id,
-            typ,
-            properties,
-        };
+        let relation = BoltRelation::new(id, start_node_id, end_node_id, typ, properties);
 
         let bytes: Bytes = relation.into_bytes(Version::V4_1).unwrap();
 
@@ -147,4 +256,52 @@ mod tests {
             vec![("name".into(), "Mark".into())].into_iter().collect()
         );
     }
+
+    #[test]
+    fn should_deserialize_a_v5_relation() {
+        let mut input = Bytes::from_static(&[
+            0xB8, 0x52, 0x2A, 0x01, 0x02, 0x83, 0x72, 0x65, 0x6C, 0xA0, 0x84, 0x35, 0x3A, 0x34,
+            0x32, 0x83, 0x34, 0x3A, 0x31, 0x83, 0x34, 0x3A, 0x32,
+        ]);
+
+        let relation: BoltRelation = BoltRelation::parse(Version::V5_0, &mut input).unwrap();
+
+        assert_eq!(relation.id, BoltInteger::new(42));
+        assert_eq!(relation.element_id, Some(BoltString::new("5:42")));
+        assert_eq!(relation.start_node_element_id, Some(BoltString::new("4:1")));
+        assert_eq!(relation.end_node_element_id, Some(BoltString::new("4:2")));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn should_deserialize_a_v5_unbounded_relation() {
+        let mut input = Bytes::from_static(&[
+            0xB4, 0x72, 0x2A, 0x83, 0x72, 0x65, 0x6C, 0xA0, 0x84, 0x35, 0x3A, 0x34, 0x32,
+        ]);
+
+        let relation: BoltUnboundedRelation =
+            BoltUnboundedRelation::parse(Version::V5_0, &mut input).unwrap();
+
+        assert_eq!(relation.id, BoltInteger::new(42));
+        assert_eq!(relation.element_id, Some(BoltString::new("5:42")));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn should_serialize_a_v5_unbounded_relation() {
+        let relation = BoltUnboundedRelation::new(
+            BoltInteger::new(42),
+            BoltString::new("rel"),
+            BoltMap::new(),
+        );
+
+        let bytes: Bytes = relation.into_bytes(Version::V5_0).unwrap();
+
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[
+                0xB4, 0x72, 0x2A, 0x83, 0x72, 0x65, 0x6C, 0xA0, 0x82, 0x34, 0x32,
+            ])
+        );
+    }
 }
@@ -30,11 +30,7 @@ impl BoltNodeBuilder {
             .ok_or_else(|| Error::missing_field("labels"))?;
         let properties = self.inner.properties.or_else(Default::default);
 
-        Ok(BoltNode {
-            id,
-            labels,
-            properties,
-        })
+        Ok(BoltNode::new(id, labels, properties))
     }
 }
 
@@ -67,13 +63,13 @@ impl BoltRelationBuilder {
         let typ = self.inner.typ.ok_or_else(|| Error::missing_field("type"))?;
         let properties = self.inner.properties.or_else(Default::default);
 
-        Ok(BoltRelation {
+        Ok(BoltRelation::new(
             id,
             start_node_id,
             end_node_id,
             typ,
             properties,
-        })
+        ))
     }
 }
 
@@ -96,11 +92,7 @@ impl BoltUnboundedRelationBuilder {
         let typ = self.inner.typ.ok_or_else(|| Error::missing_field("type"))?;
         let properties = self.inner.properties.or_else(Default::default);
 
-        Ok(BoltUnboundedRelation {
-            id,
-            typ,
-            properties,
-        })
+        Ok(BoltUnboundedRelation::new(id, typ, properties))
     }
 }
 
@@ -256,18 +248,13 @@ impl ElementBuilder {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default)]
 pub enum SetOnce<T> {
+    #[default]
     Empty,
     Set(T),
 }
 
-impl<T> Default for SetOnce<T> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SetOnceError;
 
@@ -119,8 +119,11 @@ impl<'de, const N: usize> SeqAccess<'de> for BoltDateTimeZoneIdAccess<'de, N> {
                 .map_err(|_| Error::custom("Could not convert into chrono::Datetime"))
                 .and_then(|dt| seed.deserialize(dt))
                 .map(Some),
-            Some(Fields::Seconds) => seed
-                .deserialize(self.0.seconds.value.into_deserializer())
+            Some(Fields::Seconds) => self
+                .0
+                .local_seconds()
+                .map_err(|_| Error::custom("Could not convert into local seconds"))
+                .and_then(|seconds| seed.deserialize(seconds.into_deserializer()))
                 .map(Some),
             Some(Fields::NanoSeconds) => seed
                 .deserialize(self.0.nanoseconds.value.into_deserializer())
@@ -337,6 +340,7 @@ impl DateTimeIsh for BoltDateTimeZoneId {
                 .tz_id
                 .take()
                 .ok_or_else(|| Error::missing_field("tz_id"))?,
+            utc: false,
         })
     }
 }
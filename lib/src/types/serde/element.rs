@@ -728,13 +728,13 @@ mod tests {
 
     #[test]
     fn rel_impl() {
-        let rel = BoltRelation {
-            id: BoltInteger::new(42),
-            start_node_id: BoltInteger::new(1),
-            end_node_id: BoltInteger::new(2),
-            typ: BoltString::from("KNOWS"),
-            properties: [("since".into(), 2017.into())].into_iter().collect(),
-        };
+        let rel = BoltRelation::new(
+            BoltInteger::new(42),
+            BoltInteger::new(1),
+            BoltInteger::new(2),
+            BoltString::from("KNOWS"),
+            [("since".into(), 2017.into())].into_iter().collect(),
+        );
 
         assert_eq!(
             rel.value(ElementDataKey::Id),
@@ -957,13 +957,13 @@ mod tests {
 
     #[test]
     fn rel_deser() {
-        let rel = BoltRelation {
-            id: BoltInteger::new(42),
-            start_node_id: BoltInteger::new(1),
-            end_node_id: BoltInteger::new(2),
-            typ: BoltString::from("KNOWS"),
-            properties: [("since".into(), 2017.into())].into_iter().collect(),
-        };
+        let rel = BoltRelation::new(
+            BoltInteger::new(42),
+            BoltInteger::new(1),
+            BoltInteger::new(2),
+            BoltString::from("KNOWS"),
+            [("since".into(), 2017.into())].into_iter().collect(),
+        );
 
         let id = Id::deserialize(ElementDataDeserializer::new(&rel)).unwrap();
         assert_eq!(id, Id(42));
@@ -1058,13 +1058,13 @@ mod tests {
 
     #[test]
     fn rel_deser_map() {
-        let rel = BoltRelation {
-            id: BoltInteger::new(42),
-            start_node_id: BoltInteger::new(1),
-            end_node_id: BoltInteger::new(2),
-            typ: BoltString::from("KNOWS"),
-            properties: [("since".into(), 2017.into())].into_iter().collect(),
-        };
+        let rel = BoltRelation::new(
+            BoltInteger::new(42),
+            BoltInteger::new(1),
+            BoltInteger::new(2),
+            BoltString::from("KNOWS"),
+            [("since".into(), 2017.into())].into_iter().collect(),
+        );
 
         let knows = HashMap::<ElementDataKey, BoltType>::deserialize(MapAccessDeserializer::new(
             ElementMapAccess::new(rel.items()),
@@ -17,6 +17,8 @@ mod node;
 mod path;
 mod point;
 mod rel;
+#[cfg(feature = "unstable-serde-packstream-format")]
+mod ser;
 mod time;
 mod typ;
 mod urel;
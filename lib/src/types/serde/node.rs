@@ -243,11 +243,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        BoltNode {
-            id,
-            labels,
-            properties,
-        }
+        BoltNode::new(id, labels, properties)
     }
 
     #[test]
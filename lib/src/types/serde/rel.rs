@@ -252,13 +252,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        BoltRelation {
-            id,
-            start_node_id,
-            end_node_id,
-            properties,
-            typ,
-        }
+        BoltRelation::new(id, start_node_id, end_node_id, typ, properties)
     }
 
     #[test]
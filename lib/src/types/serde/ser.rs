@@ -0,0 +1,167 @@
+//! Serializes [`BoltType`] values with the packstream serializer,
+//! e.g. the transaction metadata of requests from [`crate::bolt`].
+//!
+//! Temporal values use the UTC encoding of Bolt 5.0.
+use serde::ser::{Error as _, Serialize, SerializeMap, SerializeSeq, SerializeTupleVariant};
+
+use crate::types::BoltType;
+
+macro_rules! structure {
+    ($serializer:ident, $tag:literal, $name:literal, $($field:expr),+ $(,)?) => {{
+        let len = [$(stringify!($field)),+].len();
+        let mut ser = $serializer.serialize_tuple_variant("Bolt", $tag, $name, len)?;
+        $(ser.serialize_field(&$field)?;)+
+        ser.end()
+    }};
+}
+
+impl Serialize for BoltType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            BoltType::Null(_) => serializer.serialize_none(),
+            BoltType::Boolean(b) => serializer.serialize_bool(b.value),
+            BoltType::Integer(i) => serializer.serialize_i64(i.value),
+            BoltType::Float(f) => serializer.serialize_f64(f.value),
+            BoltType::String(s) => serializer.serialize_str(&s.value),
+            BoltType::Bytes(b) => serializer.serialize_bytes(&b.value),
+            BoltType::List(list) => {
+                let mut seq = serializer.serialize_seq(Some(list.len()))?;
+                for value in list.iter() {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            BoltType::Map(map) => {
+                let mut ser = serializer.serialize_map(Some(map.value.len()))?;
+                for (key, value) in &map.value {
+                    ser.serialize_entry(&key.value, value)?;
+                }
+                ser.end()
+            }
+            BoltType::Date(d) => structure!(serializer, 0x44, "Date", d.days.value),
+            BoltType::Time(t) => structure!(
+                serializer,
+                0x54,
+                "Time",
+                t.nanoseconds.value,
+                t.tz_offset_seconds.value,
+            ),
+            BoltType::LocalTime(t) => {
+                structure!(serializer, 0x74, "LocalTime", t.nanoseconds.value)
+            }
+            BoltType::DateTime(dt) => {
+                let seconds = dt
+                    .seconds
+                    .value
+                    .checked_sub(dt.tz_offset_seconds.value)
+                    .ok_or_else(|| S::Error::custom("datetime is out of range"))?;
+                structure!(
+                    serializer,
+                    0x49,
+                    "DateTime",
+                    seconds,
+                    dt.nanoseconds.value,
+                    dt.tz_offset_seconds.value,
+                )
+            }
+            BoltType::LocalDateTime(dt) => structure!(
+                serializer,
+                0x64,
+                "LocalDateTime",
+                dt.seconds.value,
+                dt.nanoseconds.value,
+            ),
+            BoltType::DateTimeZoneId(dt) => {
+                let seconds = dt.utc_seconds().map_err(S::Error::custom)?;
+                structure!(
+                    serializer,
+                    0x69,
+                    "DateTimeZoneId",
+                    seconds,
+                    dt.nanoseconds.value,
+                    dt.tz_id.value,
+                )
+            }
+            BoltType::Duration(d) => structure!(
+                serializer,
+                0x45,
+                "Duration",
+                d.months.value,
+                d.days.value,
+                d.seconds.value,
+                d.nanoseconds.value,
+            ),
+            BoltType::Point2D(p) => {
+                structure!(
+                    serializer,
+                    0x58,
+                    "Point2D",
+                    p.sr_id.value,
+                    p.x.value,
+                    p.y.value
+                )
+            }
+            BoltType::Point3D(p) => structure!(
+                serializer,
+                0x59,
+                "Point3D",
+                p.sr_id.value,
+                p.x.value,
+                p.y.value,
+                p.z.value,
+            ),
+            BoltType::Node(_)
+            | BoltType::Relation(_)
+            | BoltType::UnboundedRelation(_)
+            | BoltType::Path(_) => Err(S::Error::custom(
+                "nodes, relationships, and paths cannot be sent to the server",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{packstream::bolt, BoltMap};
+    use chrono::DateTime;
+
+    #[test]
+    fn serialize_values() {
+        let value = BoltType::Map(BoltMap::from_entries([(
+            "list",
+            BoltType::from(vec![BoltType::from(42), BoltType::from("a")]),
+        )]));
+
+        let bytes = crate::packstream::to_bytes(&value).unwrap();
+        let expected = bolt()
+            .tiny_map(1)
+            .tiny_string("list")
+            .tiny_list(2)
+            .tiny_int(42)
+            .tiny_string("a")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_utc_datetime() {
+        let value: BoltType = DateTime::parse_from_rfc2822("Wed, 24 Jun 2015 12:50:35 +0100")
+            .unwrap()
+            .into();
+
+        let bytes = crate::packstream::to_bytes(&value).unwrap();
+        let expected = bolt()
+            .structure(3, 0x49)
+            .int32(1_435_146_635)
+            .tiny_int(0)
+            .int16(3600)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+}
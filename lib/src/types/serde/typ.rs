@@ -1176,11 +1176,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        let node = BoltNode {
-            id,
-            labels,
-            properties,
-        };
+        let node = BoltNode::new(id, labels, properties);
         let node = BoltType::Node(node);
 
         let actual = node.to::<Person>().unwrap();
@@ -1218,13 +1214,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        let relation = BoltRelation {
-            id,
-            start_node_id,
-            end_node_id,
-            properties,
-            typ,
-        };
+        let relation = BoltRelation::new(id, start_node_id, end_node_id, typ, properties);
         let relation = BoltType::Relation(relation);
 
         let actual = relation.to::<Person>().unwrap();
@@ -1260,11 +1250,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        let relation = BoltUnboundedRelation {
-            id,
-            properties,
-            typ,
-        };
+        let relation = BoltUnboundedRelation::new(id, typ, properties);
         let relation = BoltType::UnboundedRelation(relation);
 
         let actual = relation.to::<Person>().unwrap();
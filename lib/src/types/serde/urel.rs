@@ -245,11 +245,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        BoltUnboundedRelation {
-            id,
-            properties,
-            typ,
-        }
+        BoltUnboundedRelation::new(id, typ, properties)
     }
 
     #[test]
@@ -8,12 +8,26 @@ use std::fmt::Debug;
 pub enum Version {
     V4,
     V4_1,
+    V4_2,
+    V4_3,
+    V4_4,
+    V5_0,
+    V5_1,
+    V5_2,
+    V5_3,
+    V5_4,
+    V5_5,
+    V5_6,
+    V5_7,
 }
 
 impl Version {
     pub fn supported_versions() -> Bytes {
         let mut bytes = BytesMut::with_capacity(16);
-        let versions: [u32; 4] = [0x0104, 0x0004, 0, 0];
+        // 5.7 with a range of 7 minor versions, i.e. 5.7 down to 5.0,
+        // followed by 4.4 with a range of 2 minor versions, i.e. 4.4, 4.3, and 4.2.
+        // Servers that do not understand ranges will only see 5.7 and 4.4 and skip them.
+        let versions: [u32; 4] = [0x00070705, 0x00020404, 0x0104, 0x0004];
         for version in versions.iter() {
             bytes.put_u32(*version);
         }
@@ -22,6 +36,17 @@ impl Version {
 
     pub fn parse(version_bytes: [u8; 4]) -> Result<Version> {
         match version_bytes {
+            [0, 0, 7, 5] => Ok(Version::V5_7),
+            [0, 0, 6, 5] => Ok(Version::V5_6),
+            [0, 0, 5, 5] => Ok(Version::V5_5),
+            [0, 0, 4, 5] => Ok(Version::V5_4),
+            [0, 0, 3, 5] => Ok(Version::V5_3),
+            [0, 0, 2, 5] => Ok(Version::V5_2),
+            [0, 0, 1, 5] => Ok(Version::V5_1),
+            [0, 0, 0, 5] => Ok(Version::V5_0),
+            [0, 0, 4, 4] => Ok(Version::V4_4),
+            [0, 0, 3, 4] => Ok(Version::V4_3),
+            [0, 0, 2, 4] => Ok(Version::V4_2),
             [0, 0, 1, 4] => Ok(Version::V4_1),
             [0, 0, 0, 4] => Ok(Version::V4),
             [0, 0, minor, major] => Err(Error::UnsupportedVersion(major, minor)),
@@ -36,7 +61,26 @@ mod tests {
 
     #[tokio::test]
     async fn should_parse_version() {
+        assert_eq!(Version::parse([0, 0, 7, 5]).unwrap(), Version::V5_7);
+        assert_eq!(Version::parse([0, 0, 6, 5]).unwrap(), Version::V5_6);
+        assert_eq!(Version::parse([0, 0, 5, 5]).unwrap(), Version::V5_5);
+        assert_eq!(Version::parse([0, 0, 4, 5]).unwrap(), Version::V5_4);
+        assert_eq!(Version::parse([0, 0, 3, 5]).unwrap(), Version::V5_3);
+        assert_eq!(Version::parse([0, 0, 2, 5]).unwrap(), Version::V5_2);
+        assert_eq!(Version::parse([0, 0, 1, 5]).unwrap(), Version::V5_1);
+        assert_eq!(Version::parse([0, 0, 0, 5]).unwrap(), Version::V5_0);
+        assert_eq!(Version::parse([0, 0, 4, 4]).unwrap(), Version::V4_4);
+        assert_eq!(Version::parse([0, 0, 3, 4]).unwrap(), Version::V4_3);
+        assert_eq!(Version::parse([0, 0, 2, 4]).unwrap(), Version::V4_2);
         assert_eq!(Version::parse([0, 0, 1, 4]).unwrap(), Version::V4_1);
         assert_eq!(Version::parse([0, 0, 0, 4]).unwrap(), Version::V4);
     }
+
+    #[test]
+    fn should_advertise_version_range() {
+        assert_eq!(
+            &*Version::supported_versions(),
+            &[0, 7, 7, 5, 0, 2, 4, 4, 0, 0, 1, 4, 0, 0, 0, 4]
+        );
+    }
 }
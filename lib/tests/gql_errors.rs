@@ -0,0 +1,26 @@
+use neo4rs::*;
+
+mod container;
+
+#[tokio::test]
+async fn gql_errors() {
+    let neo4j = container::Neo4jContainer::new().await;
+    let graph = neo4j.graph();
+
+    let error = graph.run(query("RETURN 1 +")).await.unwrap_err();
+    let Error::Neo4j(error) = error else {
+        panic!("expected a Neo4j error, got {error:?}");
+    };
+    assert_eq!(error.code(), "Neo.ClientError.Statement.SyntaxError");
+    assert!(!error.message().is_empty());
+
+    // GQL errors are sent since Bolt 5.7, i.e. Neo4j 5.26
+    let version = neo4j.version();
+    if (version.major, version.minor) < (5, 26) {
+        assert_eq!(error.gql_status(), None);
+        return;
+    }
+    assert!(error.gql_status().unwrap().starts_with("42"));
+    assert!(error.gql_status_description().is_some());
+    assert_eq!(error.classification(), GqlErrorClassification::ClientError);
+}
@@ -0,0 +1,11 @@
+use neo4rs::*;
+
+mod container;
+
+#[tokio::test]
+async fn managed_transactions() {
+    let neo4j = container::Neo4jContainer::new().await;
+    let graph = neo4j.graph();
+
+    include!("../include/managed_transactions.rs");
+}
@@ -0,0 +1,141 @@
+use neo4rs::*;
+
+mod container;
+
+#[tokio::test]
+async fn result_summary() {
+    let neo4j = container::Neo4jContainer::new().await;
+    let graph = neo4j.graph();
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let summary = graph
+        .run(
+            query("CREATE (:Person {id: $id})-[:KNOWS]->(:Person {id: $id})")
+                .param("id", id.clone()),
+        )
+        .await
+        .unwrap();
+    let counters = summary.counters();
+    assert_eq!(counters.nodes_created(), 2);
+    assert_eq!(counters.relationships_created(), 1);
+    assert_eq!(counters.properties_set(), 2);
+    assert_eq!(counters.labels_added(), 2);
+    assert!(counters.contains_updates());
+    assert_eq!(summary.query_type(), Some(QueryType::WriteOnly));
+    assert_eq!(summary.database(), Some("neo4j"));
+    assert!(!summary.server_address().is_empty());
+
+    let mut txn = graph.start_txn().await.unwrap();
+    let summary = txn
+        .run(query("MATCH (p:Person {id: $id}) SET p.seen = true").param("id", id.clone()))
+        .await
+        .unwrap();
+    assert_eq!(summary.counters().properties_set(), 2);
+    assert_eq!(summary.query_type(), Some(QueryType::ReadWrite));
+    txn.commit().await.unwrap();
+
+    let mut result = graph
+        .execute(query("MATCH (p:Person {id: $id}) RETURN p").param("id", id.clone()))
+        .await
+        .unwrap();
+    assert!(result.summary().is_none());
+    assert!(result.next().await.unwrap().is_some());
+    let summary = result.consume().await.unwrap();
+    assert_eq!(summary.query_type(), Some(QueryType::ReadOnly));
+    assert!(!summary.counters().contains_updates());
+    assert_eq!(result.summary(), Some(&summary));
+
+    let summary = graph
+        .run(query("MATCH (p:Person {id: $id}) DETACH DELETE p").param("id", id))
+        .await
+        .unwrap();
+    assert_eq!(summary.counters().nodes_deleted(), 2);
+    assert_eq!(summary.counters().relationships_deleted(), 1);
+}
+
+#[tokio::test]
+async fn explain_and_profile() {
+    let neo4j = container::Neo4jContainer::new().await;
+    let graph = neo4j.graph();
+
+    let summary = graph
+        .run(query("MATCH (p:Person) RETURN p").explain())
+        .await
+        .unwrap();
+    let plan = summary.plan().unwrap();
+    assert!(plan.operator_type().starts_with("ProduceResults"));
+    assert!(!plan.children().is_empty());
+    assert!(summary.profile().is_none());
+    assert!(plan.to_string().contains("+ProduceResults"));
+
+    let summary = graph
+        .run(query("UNWIND range(1, 3) AS n RETURN n").profile())
+        .await
+        .unwrap();
+    let profile = summary.profile().unwrap();
+    assert_eq!(profile.rows(), 3);
+    assert!(profile.to_string().contains("DB Hits"));
+}
+
+#[tokio::test]
+async fn notifications() {
+    use std::sync::{Arc, Mutex};
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let handler = {
+        let reported = Arc::clone(&reported);
+        move |notification: &Notification| {
+            reported
+                .lock()
+                .unwrap()
+                .push(notification.code().to_owned());
+        }
+    };
+    let config = ConfigBuilder::default().with_notification_handler(handler);
+    let neo4j = container::Neo4jContainer::from_config(config).await;
+    let graph = neo4j.graph();
+
+    let summary = graph
+        .run(query("EXPLAIN MATCH (a:Person), (b:Person) RETURN a, b"))
+        .await
+        .unwrap();
+    let [notification] = summary.notifications() else {
+        panic!(
+            "expected one notification, got {:?}",
+            summary.notifications()
+        );
+    };
+    assert_eq!(
+        notification.code(),
+        "Neo.ClientNotification.Statement.CartesianProduct"
+    );
+    assert_eq!(notification.category(), NotificationCategory::Performance);
+    assert!(notification.position().is_some());
+    assert_eq!(*reported.lock().unwrap(), [notification.code()]);
+}
+
+#[tokio::test]
+async fn notification_filter() {
+    let neo4j = container::Neo4jContainer::new().await;
+    // notification filters are available since Bolt 5.2, i.e. Neo4j 5.7
+    let version = neo4j.version();
+    if (version.major, version.minor) < (5, 7) {
+        return;
+    }
+    let graph = neo4j.graph();
+    let cartesian_product = "EXPLAIN MATCH (a:Person), (b:Person) RETURN a, b";
+
+    let filter = NotificationFilter::new().disable_categories([NotificationCategory::Performance]);
+    let session = graph.session(SessionConfig::new().notification_filter(filter));
+    let summary = session.run(query(cartesian_product)).await.unwrap();
+    assert!(summary.notifications().is_empty());
+
+    // the filter of the transaction overrides the one of the session
+    let config =
+        TxConfig::new().notification_filter(NotificationFilter::new().disable_categories([]));
+    let summary = session
+        .run_with(query(cartesian_product), config)
+        .await
+        .unwrap();
+    assert_eq!(summary.notifications().len(), 1);
+}
@@ -0,0 +1,91 @@
+use neo4rs::*;
+
+mod container;
+
+#[tokio::test]
+async fn sessions() {
+    let neo4j = container::Neo4jContainer::new().await;
+    let graph = neo4j.graph();
+
+    let session = graph.session(SessionConfig::new().database("neo4j"));
+    assert_eq!(session.database().as_deref(), Some("neo4j"));
+
+    let id = uuid::Uuid::new_v4().to_string();
+    session
+        .run(query("CREATE (p:Person {id: $id})").param("id", id.clone()))
+        .await
+        .unwrap();
+
+    // the next session continues the causal chain of the first one
+    let bookmarks = session.last_bookmarks();
+    assert!(!bookmarks.is_empty());
+
+    let session = graph.session(
+        SessionConfig::new()
+            .access_mode(AccessMode::Read)
+            .bookmarks(bookmarks),
+    );
+    let mut result = session
+        .execute(query("MATCH (p:Person) WHERE p.id = $id RETURN p.id").param("id", id))
+        .await
+        .unwrap();
+    assert!(result.next().await.unwrap().is_some());
+    assert!(result.next().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn home_database() {
+    let neo4j =
+        container::Neo4jContainer::from_config(ConfigBuilder::default().with_home_database()).await;
+    let graph = neo4j.graph();
+
+    let session = graph.session(SessionConfig::new());
+    session.run(query("RETURN 1")).await.unwrap();
+    assert_eq!(session.database().as_deref(), Some("neo4j"));
+
+    // the resolved home database is cached for later sessions
+    let session = graph.session(SessionConfig::new());
+    assert_eq!(session.database().as_deref(), Some("neo4j"));
+}
+
+#[tokio::test]
+async fn session_auth() {
+    let neo4j = container::Neo4jContainer::new().await;
+    // LOGON and LOGOFF are available since Bolt 5.1, i.e. Neo4j 5.5
+    let version = neo4j.version();
+    if (version.major, version.minor) < (5, 5) {
+        return;
+    }
+    let graph = neo4j.graph();
+
+    let user = format!("user_{}", uuid::Uuid::new_v4().simple());
+    graph
+        .run_on(
+            "system",
+            query(&format!(
+                "CREATE USER {user} SET PASSWORD 'p4ssw0rd!' CHANGE NOT REQUIRED"
+            )),
+        )
+        .await
+        .unwrap();
+
+    let session = graph.session(
+        SessionConfig::new()
+            .database("system")
+            .auth(AuthToken::basic(user.clone(), "p4ssw0rd!")),
+    );
+    let mut result = session
+        .execute(query("SHOW CURRENT USER YIELD user"))
+        .await
+        .unwrap();
+    let row = result.next().await.unwrap().unwrap();
+    assert_eq!(row.get::<String>("user").unwrap(), user);
+
+    // the connection switches back to the user of the graph before it is used again
+    let mut result = graph
+        .execute_on("system", query("SHOW CURRENT USER YIELD user"))
+        .await
+        .unwrap();
+    let row = result.next().await.unwrap().unwrap();
+    assert_ne!(row.get::<String>("user").unwrap(), user);
+}